@@ -1,7 +1,13 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
   Account,
+  BatchOptions,
+  CachePruneReport,
+  CancelResult,
   DeviceCodeResponse,
+  DownloadBatchResult,
+  DownloadPlan,
+  DownloadTask,
   FabricGameVersion,
   FabricLoaderEntry,
   FabricLoaderVersion,
@@ -14,13 +20,20 @@ import type {
   Instance,
   JavaCatalog,
   JavaDownloadInfo,
+  JavaInfo,
   JavaInstallation,
   LauncherConfig,
   Message,
   MigrationResult,
+  MirrorBenchmark,
   ModelInfo,
+  NetworkSettings,
   PastebinResponse,
+  PendingBatch,
   PendingJavaDownload,
+  ProxyConfig,
+  UrlRewriteRule,
+  VerifyReport,
   Version,
   VersionMetadata,
 } from "@/types";
@@ -41,10 +54,30 @@ export function assistantCheckHealth(): Promise<boolean> {
   return invoke<boolean>("assistant_check_health");
 }
 
+export function benchmarkMirrors(
+  urls: string[],
+  samplePath: string,
+): Promise<MirrorBenchmark[]> {
+  return invoke<MirrorBenchmark[]>("benchmark_mirrors", {
+    urls,
+    samplePath,
+  });
+}
+
 export function cancelJavaDownload(): Promise<void> {
   return invoke<void>("cancel_java_download");
 }
 
+export function cancelTask(
+  batchId: string,
+  taskId: string,
+): Promise<CancelResult> {
+  return invoke<CancelResult>("cancel_task", {
+    batchId,
+    taskId,
+  });
+}
+
 export function checkVersionInstalled(
   instanceId: string,
   versionId: string,
@@ -97,6 +130,10 @@ export function detectJava(): Promise<JavaInstallation[]> {
   return invoke<JavaInstallation[]>("detect_java");
 }
 
+export function detectJavas(): Promise<JavaInfo[]> {
+  return invoke<JavaInfo[]>("detect_javas");
+}
+
 export function downloadAdoptiumJava(
   majorVersion: number,
   imageType: string,
@@ -109,6 +146,14 @@ export function downloadAdoptiumJava(
   });
 }
 
+export function drainDownloads(
+  options: BatchOptions | null,
+): Promise<DownloadBatchResult> {
+  return invoke<DownloadBatchResult>("drain_downloads", {
+    options,
+  });
+}
+
 export function duplicateInstance(
   instanceId: string,
   newName: string,
@@ -119,6 +164,12 @@ export function duplicateInstance(
   });
 }
 
+export function enqueueDownloads(tasks: DownloadTask[]): Promise<string[]> {
+  return invoke<string[]>("enqueue_downloads", {
+    tasks,
+  });
+}
+
 export function fetchAdoptiumJava(
   majorVersion: number,
   imageType: string,
@@ -145,6 +196,10 @@ export function getActiveInstance(): Promise<Instance | null> {
   return invoke<Instance | null>("get_active_instance");
 }
 
+export function getBmclapiRewriteRules(): Promise<UrlRewriteRule[]> {
+  return invoke<UrlRewriteRule[]>("get_bmclapi_rewrite_rules");
+}
+
 export function getConfigPath(): Promise<string> {
   return invoke<string>("get_config_path");
 }
@@ -187,6 +242,10 @@ export function getInstance(instanceId: string): Promise<Instance> {
   });
 }
 
+export function getPendingBatches(): Promise<PendingBatch[]> {
+  return invoke<PendingBatch[]>("get_pending_batches");
+}
+
 export function getPendingJavaDownloads(): Promise<PendingJavaDownload[]> {
   return invoke<PendingJavaDownload[]>("get_pending_java_downloads");
 }
@@ -339,6 +398,24 @@ export function openFileExplorer(path: string): Promise<void> {
   });
 }
 
+export function pauseDownload(batchId: string): Promise<void> {
+  return invoke<void>("pause_download", {
+    batchId,
+  });
+}
+
+export function planDownload(tasks: DownloadTask[]): Promise<DownloadPlan> {
+  return invoke<DownloadPlan>("plan_download", {
+    tasks,
+  });
+}
+
+export function pruneDownloadCache(maxSize: number): Promise<CachePruneReport> {
+  return invoke<CachePruneReport>("prune_download_cache", {
+    maxSize,
+  });
+}
+
 export function readRawConfig(): Promise<string> {
   return invoke<string>("read_raw_config");
 }
@@ -351,10 +428,42 @@ export function refreshJavaCatalog(): Promise<JavaCatalog> {
   return invoke<JavaCatalog>("refresh_java_catalog");
 }
 
+export function refreshJavas(force: boolean): Promise<JavaInfo[]> {
+  return invoke<JavaInfo[]>("refresh_javas", {
+    force,
+  });
+}
+
+export function removeDownload(id: string): Promise<boolean> {
+  return invoke<boolean>("remove_download", {
+    id,
+  });
+}
+
+export function resumeBatch(batchId: string): Promise<DownloadBatchResult> {
+  return invoke<DownloadBatchResult>("resume_batch", {
+    batchId,
+  });
+}
+
+export function resumeDownload(batchId: string): Promise<void> {
+  return invoke<void>("resume_download", {
+    batchId,
+  });
+}
+
 export function resumeJavaDownloads(): Promise<JavaInstallation[]> {
   return invoke<JavaInstallation[]>("resume_java_downloads");
 }
 
+export function retryFailed(
+  batchId: string,
+): Promise<DownloadBatchResult | null> {
+  return invoke<DownloadBatchResult | null>("retry_failed", {
+    batchId,
+  });
+}
+
 export function saveRawConfig(content: string): Promise<void> {
   return invoke<void>("save_raw_config", {
     content,
@@ -373,6 +482,18 @@ export function setActiveInstance(instanceId: string): Promise<void> {
   });
 }
 
+export function setDownloadSpeedLimit(bytesPerSec: number): Promise<void> {
+  return invoke<void>("set_download_speed_limit", {
+    bytesPerSec,
+  });
+}
+
+export function setUrlRewriteRules(rules: UrlRewriteRule[]): Promise<void> {
+  return invoke<void>("set_url_rewrite_rules", {
+    rules,
+  });
+}
+
 export function startGame(
   instanceId: string,
   versionId: string,
@@ -387,14 +508,34 @@ export function startMicrosoftLogin(): Promise<DeviceCodeResponse> {
   return invoke<DeviceCodeResponse>("start_microsoft_login");
 }
 
+export function testProxy(): Promise<number> {
+  return invoke<number>("test_proxy");
+}
+
 export function updateInstance(instance: Instance): Promise<void> {
   return invoke<void>("update_instance", {
     instance,
   });
 }
 
+export function updateNetworkSettings(
+  proxy: ProxyConfig,
+  network: NetworkSettings,
+): Promise<void> {
+  return invoke<void>("update_network_settings", {
+    proxy,
+    network,
+  });
+}
+
 export function uploadToPastebin(content: string): Promise<PastebinResponse> {
   return invoke<PastebinResponse>("upload_to_pastebin", {
     content,
   });
 }
+
+export function verifyFiles(tasks: DownloadTask[]): Promise<VerifyReport> {
+  return invoke<VerifyReport>("verify_files", {
+    tasks,
+  });
+}
@@ -155,7 +155,7 @@ export const useLogsStore = create<LogsState>((set, get) => ({
       get().addLog(
         "info",
         "Downloader",
-        `Starting batch download of ${e.payload} files...`,
+        `Starting batch download of ${e.payload.totalFiles} files...`,
       );
     });
 
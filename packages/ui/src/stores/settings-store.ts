@@ -122,6 +122,22 @@ const defaultSettings: LauncherConfig = {
   width: 854,
   height: 480,
   downloadThreads: 32,
+  adaptiveDownloadThreads: false,
+  downloadSpeedLimit: 0,
+  urlRewriteRules: [],
+  proxy: { mode: "system" },
+  network: {
+    httpVersion: "auto",
+    tcpKeepaliveSecs: null,
+    poolIdleTimeoutSecs: 90,
+    caCertificates: [],
+    insecureHosts: [],
+  },
+  downloadStateMaxAgeDays: 7,
+  partialFilePolicy: null,
+  orphanFileMaxAgeDays: 7,
+  downloadCacheDir: null,
+  downloadShutdownGraceSecs: 5,
   enableGpuAcceleration: false,
   enableVisualEffects: true,
   activeEffect: "constellation",
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NetworkSettings, PartialFilePolicy, ProxyConfig, UrlRewriteRule } from "./downloader";
 
 export type AssistantConfig = {
   enabled: boolean;
@@ -47,6 +48,16 @@ export type LauncherConfig = {
   width: number;
   height: number;
   downloadThreads: number;
+  adaptiveDownloadThreads: boolean;
+  downloadSpeedLimit: number;
+  urlRewriteRules: Array<UrlRewriteRule>;
+  proxy: ProxyConfig;
+  network: NetworkSettings;
+  downloadStateMaxAgeDays: number;
+  partialFilePolicy: PartialFilePolicy | null;
+  orphanFileMaxAgeDays: number;
+  downloadCacheDir: string | null;
+  downloadShutdownGraceSecs: number;
   customBackgroundPath: string | null;
   enableGpuAcceleration: boolean;
   enableVisualEffects: boolean;
@@ -1,5 +1,256 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
+/**
+ * Totals of a running batch, sent as `download-aggregate` every `progress_interval` when
+ * `DownloadOptions::emit_mode` isn't `PerFile`
+ */
+export type AggregateProgress = {
+  batchId: string;
+  completedFiles: number;
+  totalFiles: number;
+  totalBytes: number;
+  totalDownloadedBytes: number;
+  totalExpectedBytes: number;
+  unknownSizeFiles: number;
+  totalSpeedBps: number;
+  /**
+   * `None` while the speed or the size of some file is unknown
+   */
+  etaSeconds: number | null;
+  /**
+   * Number of tasks downloading or verifying a file
+   */
+  inFlight: number;
+  /**
+   * Names of the last files that finished, oldest first
+   */
+  recentlyCompleted: Array<string>;
+};
+
+/**
+ * Archive formats a task can be extracted from
+ */
+export type ArchiveKind = "zip" | "tarGz";
+
+/**
+ * Credentials sent in the `Authorization` header of a task's requests. `Debug` leaves the
+ * secrets out, so tasks can be logged.
+ */
+export type AuthSpec =
+  | { type: "bearer"; token: string }
+  | { type: "basic"; user: string; pass: string };
+
+/**
+ * Batch settings sent by the frontend. Fields left out keep the value from the launcher
+ * settings, so `{ maxConcurrent: 4 }` only changes the concurrency.
+ */
+export type BatchOptions = {
+  /**
+   * Fixed number of concurrent downloads, turns the adaptive concurrency off
+   */
+  maxConcurrent: number | null;
+  adaptiveConcurrency: boolean | null;
+  maxRetries: number | null;
+  connectTimeoutSecs: number | null;
+  readTimeoutSecs: number | null;
+  stallTimeoutSecs: number | null;
+  verifyExisting: boolean | null;
+  onError: PartialFilePolicy | null;
+  existingFilePolicy: ExistingFilePolicy | null;
+  ordering: TaskOrdering | null;
+  manifestPath: string | null;
+  emitMode: ProgressEmitMode | null;
+  preflight: boolean | null;
+  failurePolicy: FailurePolicy | null;
+  /**
+   * Id for the batch, so it can be paused or cancelled before the command returns
+   */
+  batchId: string | null;
+};
+
+/**
+ * Result of `prune_cache`
+ */
+export type CachePruneReport = {
+  removedFiles: number;
+  freedBytes: number;
+  /**
+   * Size of the entries left in the cache
+   */
+  remainingBytes: number;
+};
+
+/**
+ * Outcome of cancelling a single task of a batch
+ */
+export type CancelResult = "cancelled" | "alreadyFinished" | "notFound";
+
+/**
+ * Expected hash of a file, in the algorithm its source publishes: md5 and sha1 for Maven
+ * repositories, sha256 for Adoptium, sha512 for Modrinth
+ */
+export type Checksum =
+  | { algorithm: "md5"; digest: string }
+  | { algorithm: "sha1"; digest: string }
+  | { algorithm: "sha256"; digest: string }
+  | { algorithm: "sha512"; digest: string };
+
+/**
+ * Payload of `download-warning`, sent when the disk usage of a batch isn't fully known
+ */
+export type DiskSpaceWarning = {
+  batchId: string;
+  /**
+   * Bytes needed by the files with a known size
+   */
+  required: bigint;
+  available: bigint;
+  /**
+   * Number of files whose size is unknown and not part of `required`
+   */
+  unknownSizeFiles: number;
+};
+
+/**
+ * Outcome of a `download_files` batch, also sent as the `download-complete` payload
+ */
+export type DownloadBatchResult = {
+  batchId: string;
+  /**
+   * Number of files that were downloaded or already valid
+   */
+  succeeded: number;
+  failed: Array<FailedTask>;
+  /**
+   * Number of tasks removed from the queue or cancelled while they ran
+   */
+  removed: number;
+  /**
+   * Downloaded files whose server sent no Content-Length, so truncation couldn't be detected
+   */
+  unknownLength: Array<string>;
+  /**
+   * Files with `fetch_sidecar_hash` set whose `.sha1` file was missing, so they weren't
+   * verified
+   */
+  unverified: Array<string>;
+  /**
+   * Number of failed files, for offering a retry
+   */
+  errored: number;
+  /**
+   * Files that already existed and were kept instead of downloaded, because they were
+   * valid, unchanged on the server or `DownloadTask::overwrite` is `IfMissing`
+   */
+  kept: Array<string>;
+  summary: DownloadSummary;
+  /**
+   * Why the batch stopped early, if its `FailurePolicy` gave up
+   */
+  aborted: DownloadError | null;
+  /**
+   * What the batch placed on disk, if `DownloadOptions::manifest_path` is set
+   */
+  manifest: DownloadManifest | null;
+  /**
+   * Why the manifest couldn't be written. The downloads themselves aren't affected.
+   */
+  manifestError: string | null;
+};
+
+/**
+ * Reason a download failed. Sent to the frontend as an object tagged with `type`, so it
+ * can tell the failures apart and word them itself; `Display` gives the message for logs.
+ */
+export type DownloadError =
+  | { type: "request"; message: string }
+  | {
+      type: "httpStatus";
+      code: number;
+      /**
+       * Start of the response body, e.g. the error page
+       */
+      body: string;
+    }
+  | {
+      type: "io";
+      /**
+       * `std::io::ErrorKind` of the failure, e.g. `PermissionDenied`
+       */
+      kind: string;
+      message: string;
+    }
+  | { type: "hashMismatch"; expected: string; actual: string }
+  | { type: "sizeMismatch"; expected: bigint; actual: bigint }
+  | { type: "cancelled" }
+  | { type: "aborted"; id: string; path: string; reason: DownloadError }
+  | { type: "truncated"; expected: bigint; received: bigint }
+  | { type: "stalled"; idle_ms: bigint; received: bigint }
+  | { type: "diskFull"; available: bigint }
+  | { type: "insufficientDiskSpace"; required: bigint; available: bigint }
+  | { type: "invalidDataUrl"; message: string }
+  | { type: "destinationIsDirectory"; path: string }
+  | { type: "parentIsFile"; path: string }
+  | {
+      type: "redirect";
+      message: string;
+      /**
+       * Where the redirect chain ended up
+       */
+      final_url: string;
+    }
+  | { type: "invalidTask"; message: string }
+  | { type: "extraction"; message: string }
+  | { type: "other"; message: string };
+
+/**
+ * Payload of `download-error`, sent once for every task that failed after all retries,
+ * and for tasks removed from the queue while they ran
+ */
+export type DownloadErrorEvent = {
+  batchId: string;
+  id: string;
+  file: string;
+  /**
+   * URL of the last source that was tried
+   */
+  url: string;
+  kind: DownloadErrorKind;
+  message: string;
+  error: DownloadError;
+  /**
+   * Number of the last attempt, 0 if the task failed before any request was made
+   */
+  attempt: number;
+  /**
+   * URL the last response came from, if the source redirected
+   */
+  finalUrl: string | null;
+};
+
+/**
+ * Category of a failed task, for the `download-error` event
+ */
+export type DownloadErrorKind =
+  | "network"
+  | "httpStatus"
+  | "io"
+  | "hashMismatch"
+  | "cancelled"
+  | "redirect";
+
+/**
+ * Record of the files of a batch, written to `DownloadOptions::manifest_path` for auditing
+ * and delta updates
+ */
+export type DownloadManifest = {
+  batchId: string;
+  /**
+   * Every task that wasn't removed, by path
+   */
+  files: Array<ManifestEntry>;
+};
+
 /**
  * Metadata for resumable downloads stored in .part.meta file
  */
@@ -13,11 +264,53 @@ export type DownloadMetadata = {
   segments: Array<DownloadSegment>;
 };
 
+/**
+ * Tasks of a batch that still have to be downloaded, as returned by `plan_download`
+ */
+export type DownloadPlan = {
+  /**
+   * Tasks whose file is missing, corrupt or can't be checked, in their original order
+   */
+  tasks: Array<DownloadTask>;
+  /**
+   * Combined size of the tasks whose size is known
+   */
+  totalBytes: number;
+  /**
+   * Number of tasks without a known size, which `total_bytes` leaves out
+   */
+  unknownSize: number;
+};
+
 /**
  * Download queue for persistence
  */
 export type DownloadQueue = { pendingDownloads: Array<PendingJavaDownload> };
 
+/**
+ * Bandwidth of a running batch, sent as `download-sample` at a fixed interval for drawing a
+ * speed graph
+ */
+export type DownloadSample = {
+  batchId: string;
+  /**
+   * Bytes received from the network since the previous sample, per second
+   */
+  bytesPerSec: number;
+  /**
+   * Number of tasks downloading or verifying a file
+   */
+  activeTasks: number;
+  /**
+   * Number of tasks waiting for a free slot or still in the queue
+   */
+  queuedTasks: number;
+  /**
+   * Number of files that may download at once, changed by the adaptive concurrency
+   */
+  concurrency: number;
+};
+
 /**
  * A download segment for multi-segment parallel downloading
  */
@@ -28,13 +321,224 @@ export type DownloadSegment = {
   completed: boolean;
 };
 
+/**
+ * Payload of `download-start`, sent before the first file of a batch starts
+ */
+export type DownloadStartEvent = {
+  batchId: string;
+  totalFiles: number;
+  /**
+   * Combined size of the files with a known size
+   */
+  totalExpectedBytes: bigint;
+  /**
+   * Number of files whose size is unknown and not part of `total_expected_bytes`
+   */
+  unknownSizeFiles: number;
+  /**
+   * The files of the batch in the order they were queued, so the list can be shown
+   * before they start. Tasks added to the queue while it runs aren't included.
+   */
+  files: Array<DownloadStartFile>;
+};
+
+/**
+ * A file of a batch as listed in `download-start`; `id` matches its later events
+ */
+export type DownloadStartFile = {
+  id: string;
+  file: string;
+  group: string | null;
+  /**
+   * Expected size in bytes, `None` while it's unknown
+   */
+  size: number | null;
+};
+
+/**
+ * Statistics of a finished batch, also sent as the `download-summary` payload
+ */
+export type DownloadSummary = {
+  batchId: string;
+  elapsedMs: number;
+  /**
+   * Bytes received from the network, including those of failed attempts
+   */
+  transferredBytes: number;
+  /**
+   * `transferred_bytes` over the elapsed time
+   */
+  averageSpeedBps: number;
+  /**
+   * Number of files that were downloaded
+   */
+  finished: number;
+  /**
+   * Number of files that were already valid or unchanged on the server
+   */
+  skipped: number;
+  failed: number;
+  /**
+   * Number of times an existing or just downloaded file didn't have the expected hash
+   * and was downloaded again
+   */
+  redownloaded: number;
+  /**
+   * Number of files copied from another file of the batch with the same URL and checksum
+   */
+  copied: number;
+  /**
+   * Bytes those files didn't have to download
+   */
+  savedBytes: number;
+  /**
+   * `host:port` with the lowest transfer speed, `None` if nothing was downloaded over the network
+   */
+  slowestHost: string | null;
+  /**
+   * Number of progress updates that were coalesced or dropped because the frontend
+   * didn't keep up with the events
+   */
+  droppedEvents: number;
+};
+
 export type DownloadTask = {
+  /**
+   * http(s) URL, or a `file://` URL copied from the local disk like a download
+   */
   url: string;
   path: string;
-  sha1: string | null;
-  sha256: string | null;
+  /**
+   * Expected hash of the file. Without it, the `sha256` or else the `sha1` field of older
+   * versions is read into it.
+   */
+  checksum: Checksum | null;
+  /**
+   * Expected file size in bytes, if known
+   */
+  size: bigint | null;
+  /**
+   * Mirrors tried in order when `url` fails or serves corrupt data
+   */
+  fallbackUrls: Array<string>;
+  /**
+   * Identifies the task in progress events; `download_files` assigns the task's index when
+   * it's not set
+   */
+  id: string | null;
+  /**
+   * Extra request headers, e.g. an API key required by the host
+   */
+  headers: { [key in string]?: string } | null;
+  /**
+   * Tasks with a higher priority are started first
+   */
+  priority: number;
+  /**
+   * Directory the downloaded archive is unpacked into once it's verified
+   */
+  extractTo: string | null;
+  /**
+   * Format of the archive, guessed from the file name when not set
+   */
+  archiveKind: ArchiveKind | null;
+  /**
+   * Remember the ETag and Last-Modified of the response, so the next download of an
+   * unchanged file is answered with 304 Not Modified and skipped
+   */
+  cacheable: boolean;
+  /**
+   * Make the file executable once it's downloaded, or every extracted file with
+   * `extract_to` set. Has no effect on Windows.
+   */
+  executable: boolean;
+  /**
+   * Without a `checksum`, fetch the hash from a `.sha1` file next to the URL and verify
+   * against it. Tasks whose sidecar is missing are downloaded unverified and listed in
+   * `DownloadBatchResult::unverified`.
+   */
+  fetchSidecarHash: boolean;
+  /**
+   * Label the task's progress is summed up under in `download-group-progress` events,
+   * e.g. "libraries" or "assets"
+   */
+  group: string | null;
+  /**
+   * What to do when the file already exists and there's no `checksum` to check it
+   * against. Falls back to `DownloadOptions::existing_file_policy`, and when neither is
+   * set, tasks with a `size` are checked by it.
+   */
+  existingFilePolicy: ExistingFilePolicy | null;
+  /**
+   * When an existing file is replaced. Unset, tasks with a `checksum` use
+   * `IfHashMismatch`, and others keep their file as `existing_file_policy` says, which is
+   * `IfMissing` unless a size or cached validators can tell the file is outdated.
+   */
+  overwrite: OverwritePolicy | null;
+  /**
+   * Credentials for the host of `url`. They're only sent to that host, not to mirrors,
+   * fallbacks on other hosts or redirect targets elsewhere, and are redacted from errors.
+   */
+  auth: AuthSpec | null;
+};
+
+/**
+ * What to do with a file that already exists when its task has no hash to verify it
+ */
+export type ExistingFilePolicy =
+  | "overwrite"
+  | "skipIfExists"
+  | "skipIfSizeMatches";
+
+/**
+ * A task that could not be downloaded, with the reason
+ */
+export type FailedTask = {
+  id: string;
+  url: string;
+  path: string;
+  kind: DownloadErrorKind;
+  error: DownloadError;
+  /**
+   * URL the last response came from, if the source redirected
+   */
+  finalUrl: string | null;
+  /**
+   * Bytes kept in the `.part` file, which `retry_failed` continues from
+   */
+  partialBytes: number | null;
 };
 
+/**
+ * How many failed tasks a batch tolerates before it stops
+ */
+export type FailurePolicy = "continueAll" | "failFast" | { maxErrors: number };
+
+/**
+ * Progress of the tasks of a batch sharing a `DownloadTask::group`, sent as
+ * `download-group-progress` whenever it changes
+ */
+export type GroupProgress = {
+  batchId: string;
+  group: string;
+  /**
+   * Files downloaded or already valid
+   */
+  completedFiles: number;
+  failedFiles: number;
+  totalFiles: number;
+  downloadedBytes: number;
+  /**
+   * Sizes of the files known up front or learned while they download
+   */
+  expectedBytes: number;
+};
+
+/**
+ * HTTP version negotiation of the download client
+ */
+export type HttpVersion = "auto" | "http1Only" | "http2PriorKnowledge";
+
 /**
  * Progress event for Java download
  */
@@ -48,6 +552,108 @@ export type JavaDownloadProgress = {
   percentage: number;
 };
 
+export type ManifestEntry = {
+  id: string;
+  path: string;
+  /**
+   * URL the file was served from, after mirrors, fallbacks and redirects
+   */
+  url: string;
+  status: ManifestStatus;
+  /**
+   * Size of the file on disk, `None` for failed tasks
+   */
+  size: number | null;
+  /**
+   * sha1 of the file on disk, `None` for failed tasks
+   */
+  sha1: string | null;
+};
+
+/**
+ * How the file of a manifest entry got there
+ */
+export type ManifestStatus =
+  | "downloaded"
+  | "skipped"
+  | "cached"
+  | "copied"
+  | "failed";
+
+/**
+ * Speed of a download mirror, measured by `benchmark_mirrors`
+ */
+export type MirrorBenchmark = {
+  /**
+   * Base URL of the mirror, as given
+   */
+  url: string;
+  /**
+   * Time until the response headers arrived, `None` if the request failed
+   */
+  latencyMs: number | null;
+  /**
+   * Bytes of the sample received
+   */
+  bytes: number;
+  /**
+   * Transfer speed of the body, `None` if the request failed
+   */
+  bytesPerSec: number | null;
+  error: string | null;
+};
+
+/**
+ * Connection settings of the HTTP client shared by all downloads
+ */
+export type NetworkSettings = {
+  httpVersion: HttpVersion;
+  /**
+   * Interval of TCP keepalive probes in seconds, `None` disables them
+   */
+  tcpKeepaliveSecs: number | null;
+  /**
+   * Idle connections are closed after this many seconds, `None` keeps them open
+   */
+  poolIdleTimeoutSecs: number | null;
+  /**
+   * PEM files with CA certificates trusted on top of the system ones, e.g. for a LAN
+   * mirror with a self-signed certificate
+   */
+  caCertificates: Array<string>;
+  /**
+   * Hosts whose invalid TLS certificates are accepted. The hosts of Mojang and of the
+   * loader, Java and mod providers can't be added.
+   */
+  insecureHosts: Array<string>;
+};
+
+/**
+ * When the existing file of a task is replaced by a download
+ */
+export type OverwritePolicy = "always" | "ifMissing" | "ifHashMismatch";
+
+/**
+ * What happens to the `.part` file of a task that failed
+ */
+export type PartialFilePolicy = "keepPartial" | "deletePartial";
+
+/**
+ * Outstanding tasks of a batch, saved while it runs so it can be resumed after a restart
+ */
+export type PendingBatch = {
+  batchId: string;
+  /**
+   * Unix time the batch started, in seconds
+   */
+  createdAt: number;
+  /**
+   * Unix time the state was last saved, in seconds
+   */
+  updatedAt: number;
+  tasks: Array<PendingTask>;
+};
+
 /**
  * Pending download task for queue persistence
  */
@@ -62,12 +668,147 @@ export type PendingJavaDownload = {
   createdAt: bigint;
 };
 
+/**
+ * A task of a `PendingBatch`
+ */
+export type PendingTask = {
+  task: DownloadTask;
+  status: string;
+  /**
+   * Bytes of the `.part` file a resumed download continues from
+   */
+  partOffset: number;
+};
+
+/**
+ * Which progress events a batch sends
+ */
+export type ProgressEmitMode = "perFile" | "aggregateOnly" | "both";
+
 export type ProgressEvent = {
+  /**
+   * Id of the `download_files` batch this event belongs to
+   */
+  batchId: string;
+  /**
+   * Id of the task, unique within the batch
+   */
+  id: string;
+  /**
+   * Destination path of the file
+   */
+  path: string;
+  /**
+   * File name, for display
+   */
   file: string;
+  /**
+   * URL the file is currently downloaded from
+   */
+  url: string;
   downloaded: bigint;
   total: bigint;
   status: string;
+  /**
+   * Download attempt number (1 for the first try, 0 before any request was made)
+   */
+  attempt: number;
   completedFiles: number;
   totalFiles: number;
+  /**
+   * Combined size of the files in the batch whose size is known up front
+   */
+  totalBytes: bigint;
   totalDownloadedBytes: bigint;
+  /**
+   * Denominator for the overall progress: `total_bytes` plus the sizes learned from
+   * Content-Length headers or files found on disk
+   */
+  totalExpectedBytes: bigint;
+  /**
+   * Number of files whose size is still unknown, which only count towards `total_files`
+   */
+  unknownSizeFiles: number;
+  /**
+   * Transfer speed of this file, averaged over the last few seconds
+   */
+  speedBps: bigint;
+  /**
+   * Transfer speed of the whole batch
+   */
+  totalSpeedBps: bigint;
+  /**
+   * Estimated seconds until this file finishes, `None` when its size is unknown
+   */
+  etaSeconds: bigint | null;
+  /**
+   * Why the file failed, set on "Error" events. "Redownloading" events carry the hash
+   * mismatch that made the file be downloaded again.
+   */
+  error: DownloadError | null;
+  /**
+   * Seconds the server asked to wait before the next request, set on "Throttled" events
+   */
+  waitSeconds: bigint | null;
+  /**
+   * URL the file was served from after redirects, set on "Finished" events when it
+   * differs from `url`
+   */
+  finalUrl: string | null;
+  /**
+   * `DownloadTask::group` of the file
+   */
+  group: string | null;
+  /**
+   * Set on "Downloading" events while the size of the file is unknown, e.g. because the
+   * server compressed the response. `total` is 0 then.
+   */
+  indeterminate: boolean;
+  /**
+   * How the file was checked, set on "Finished", "Skipped", "Cached" and "Copied" events
+   */
+  verified: Verification | null;
+};
+
+/**
+ * Proxy used for download requests
+ */
+export type ProxyConfig =
+  | { mode: "none" }
+  | { mode: "system" }
+  | {
+      mode: "manual";
+      url: string;
+      username: string | null;
+      password: string | null;
+    };
+
+/**
+ * Order the tasks of a batch are started in, within the same priority
+ */
+export type TaskOrdering = "asGiven" | "largestFirst" | "smallestFirst";
+
+/**
+ * Replace a URL prefix with a mirror, e.g. Mojang's CDN with BMCLAPI
+ */
+export type UrlRewriteRule = { prefix: string; replacement: string };
+
+/**
+ * How thoroughly a finished file was checked
+ */
+export type Verification = "hash" | "sizeOnly" | "none";
+
+/**
+ * Result of `verify_files`, listing the destination paths by state
+ */
+export type VerifyReport = {
+  /**
+   * Files passing their checks, including existing files the task has no size or hash for
+   */
+  valid: Array<string>;
+  missing: Array<string>;
+  /**
+   * Files with the wrong size or hash, or that can't be read
+   */
+  corrupt: Array<string>;
 };
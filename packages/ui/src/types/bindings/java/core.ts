@@ -17,6 +17,44 @@ export type JavaDownloadInfo = {
   image_type: string;
 };
 
+/**
+ * What running a Java executable told about it
+ */
+export type JavaInfo = {
+  path: string;
+  /**
+   * Full version, e.g. "17.0.8" or "1.8.0_381"
+   */
+  version: string;
+  /**
+   * Feature release, e.g. 17 or 8
+   */
+  major: number;
+  vendor: string;
+  /**
+   * "x64", "x86", "aarch64" or the `os.arch` of other platforms
+   */
+  arch: string;
+  is64bit: boolean;
+  /**
+   * Where the executable was found, e.g. "PATH" or "JAVA_HOME"
+   */
+  sources: Array<string>;
+  /**
+   * Other paths leading to the same executable
+   */
+  aliases: Array<string>;
+  /**
+   * Whether it comes from Flatpak or Snap, whose sandbox may keep it from the game directory
+   */
+  sandboxed: boolean;
+  /**
+   * Whether this process can run it. `false` if probing it failed, or if it lacks the
+   * permission to be run, which Javas read from their `release` file are only checked for.
+   */
+  executable: boolean;
+};
+
 export type JavaInstallation = {
   path: string;
   version: string;
@@ -24,6 +62,10 @@ export type JavaInstallation = {
   vendor: string;
   source: string;
   is64bit: boolean;
+  /**
+   * Whether this process can run it, see [`JavaInfo::executable`]
+   */
+  executable: boolean;
 };
 
 export type JavaReleaseInfo = {
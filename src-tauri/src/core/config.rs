@@ -1,3 +1,4 @@
+use crate::core::downloader::{NetworkSettings, PartialFilePolicy, ProxyConfig, UrlRewriteRule};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -85,7 +86,18 @@ pub struct LauncherConfig {
     pub java_path: String,
     pub width: u32,
     pub height: u32,
-    pub download_threads: u32, // concurrent download threads (1-128)
+    pub download_threads: u32, // concurrent download threads (1-64)
+    pub adaptive_download_threads: bool, // adjust the download threads to the connection, starting at download_threads
+    #[ts(type = "number")]
+    pub download_speed_limit: u64, // bytes per second, 0 = unlimited
+    pub url_rewrite_rules: Vec<UrlRewriteRule>, // download mirrors, e.g. BMCLAPI
+    pub proxy: ProxyConfig,
+    pub network: NetworkSettings, // HTTP version and connection tuning of the download client
+    pub download_state_max_age_days: u32, // saved state of interrupted batches is dropped after this
+    pub partial_file_policy: Option<PartialFilePolicy>, // None = keep them only for tasks with a hash
+    pub orphan_file_max_age_days: u32, // leftover .part/.tmp files in instances are deleted after this
+    pub download_cache_dir: Option<String>, // shared store of downloaded files by sha1, None = disabled
+    pub download_shutdown_grace_secs: u32, // on exit, running downloads get this long to save their progress
     pub custom_background_path: Option<String>,
     pub enable_gpu_acceleration: bool,
     pub enable_visual_effects: bool,
@@ -110,6 +122,16 @@ impl Default for LauncherConfig {
             width: 854,
             height: 480,
             download_threads: 32,
+            adaptive_download_threads: false,
+            download_speed_limit: 0,
+            url_rewrite_rules: Vec::new(),
+            proxy: ProxyConfig::default(),
+            network: NetworkSettings::default(),
+            download_state_max_age_days: 7,
+            partial_file_policy: None,
+            orphan_file_max_age_days: 7,
+            download_cache_dir: None,
+            download_shutdown_grace_secs: 5,
             custom_background_path: None,
             enable_gpu_acceleration: false,
             enable_visual_effects: true,
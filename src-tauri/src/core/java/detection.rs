@@ -7,78 +7,577 @@ use std::time::Duration;
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
+use crate::core::java::JavaInfo;
 use crate::core::java::strip_unc_prefix;
 
 const WHICH_TIMEOUT: Duration = Duration::from_secs(2);
 
-/// Scans a directory for Java installations, filtering out symlinks
+/// Registry keys JDK installers record their location under, with the value holding it
+#[cfg(target_os = "windows")]
+const REGISTRY_JAVA_KEYS: &[(&str, &str)] = &[
+    ("SOFTWARE\\JavaSoft\\JDK", "JavaHome"),
+    ("SOFTWARE\\JavaSoft\\Java Development Kit", "JavaHome"),
+    ("SOFTWARE\\JavaSoft\\Java Runtime Environment", "JavaHome"),
+    ("SOFTWARE\\Eclipse Adoptium\\JDK", "Path"),
+    ("SOFTWARE\\Eclipse Adoptium\\JRE", "Path"),
+    ("SOFTWARE\\Azul Systems\\Zulu", "InstallationPath"),
+];
+
+/// Finds the Java executables of the apps installed with Scoop
 ///
-/// # Arguments
-/// * `base_dir` - Base directory to scan (e.g., mise or SDKMAN java dir)
-/// * `should_skip` - Predicate to determine if an entry should be skipped
+/// Both the user root (`SCOOP`, or `%USERPROFILE%\scoop`) and the global root (`SCOOP_GLOBAL`,
+/// or `%ProgramData%\scoop`) are scanned for `apps\<app>\current\bin\java.exe`. The
+/// `current` junction is resolved when the candidates are merged.
+#[cfg(target_os = "windows")]
+fn find_scoop_javas() -> Vec<PathBuf> {
+    let user_root = std::env::var("SCOOP").ok().or_else(|| {
+        std::env::var("USERPROFILE")
+            .ok()
+            .map(|profile| format!("{}\\scoop", profile))
+    });
+    let global_root = std::env::var("SCOOP_GLOBAL").ok().or_else(|| {
+        std::env::var("ProgramData")
+            .ok()
+            .map(|data| format!("{}\\scoop", data))
+    });
+
+    [user_root, global_root]
+        .into_iter()
+        .flatten()
+        .flat_map(|root| scan_scoop_apps(Path::new(&root)))
+        .collect()
+}
+
+/// Lists `apps/<app>/current/bin/java.exe` under a Scoop root
+#[cfg(any(target_os = "windows", test))]
+fn scan_scoop_apps(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(root.join("apps")) else {
+        return Vec::new();
+    };
+    let mut javas: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path().join("current").join("bin").join("java.exe"))
+        .filter(|java_path| java_path.exists())
+        .collect();
+    javas.sort();
+    javas
+}
+
+/// Finds the Java executables of the packages installed with Chocolatey
 ///
-/// # Returns
-/// First valid Java installation found, or `None`
-fn scan_java_dir<F>(base_dir: &Path, should_skip: F) -> Option<PathBuf>
-where
-    F: Fn(&std::fs::DirEntry) -> bool,
-{
-    std::fs::read_dir(base_dir)
-        .ok()?
+/// Packages unpack under `%ChocolateyInstall%\lib\<package>`, with the JDK a few directories
+/// down, e.g. `lib\openjdk\tools\jdk-17.0.8\bin\java.exe`. Packages that run an installer
+/// instead end up in `Program Files`, which is scanned already.
+#[cfg(target_os = "windows")]
+fn find_chocolatey_javas() -> Vec<PathBuf> {
+    let root = std::env::var("ChocolateyInstall")
+        .unwrap_or_else(|_| "C:\\ProgramData\\chocolatey".to_string());
+    let root = Path::new(&root);
+
+    let mut javas = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(root.join("lib")) {
+        for entry in entries.flatten() {
+            find_java_executables(&entry.path(), "java.exe", CHOCOLATEY_SCAN_DEPTH, &mut javas);
+        }
+    }
+    // A shim, resolved to the package's executable when the candidates are probed
+    let shim = root.join("bin").join("java.exe");
+    if shim.exists() {
+        javas.push(shim);
+    }
+    javas
+}
+
+/// How many directories below a Chocolatey package its `bin\java.exe` is looked for
+#[cfg(any(target_os = "windows", test))]
+const CHOCOLATEY_SCAN_DEPTH: usize = 4;
+
+/// Collects the `bin/<bin_name>` of `dir` and of its subdirectories, `depth` levels down
+#[cfg(any(target_os = "windows", target_os = "linux", test))]
+fn find_java_executables(dir: &Path, bin_name: &str, depth: usize, javas: &mut Vec<PathBuf>) {
+    let java_path = dir.join("bin").join(bin_name);
+    if java_path.is_file() {
+        javas.push(java_path);
+        return;
+    }
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut subdirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && !path.is_symlink())
+        .collect();
+    subdirs.sort();
+    for subdir in subdirs {
+        find_java_executables(&subdir, bin_name, depth - 1, javas);
+    }
+}
+
+/// Sources of Javas that run sandboxed, which may not reach every game directory
+pub const SANDBOXED_SOURCES: &[&str] = &["Flatpak", "Snap"];
+
+/// Finds the OpenJDK extensions installed with Flatpak under `root`, e.g. `/var/lib/flatpak`
+///
+/// Path: `<root>/runtime/org.freedesktop.Sdk.Extension.openjdk<version>/<arch>/<branch>/active/files/`,
+/// with the JDK a directory or two further down, e.g. `jvm/openjdk-17/bin/java`.
+#[cfg(any(target_os = "linux", test))]
+fn find_flatpak_javas(root: &Path) -> Vec<PathBuf> {
+    let mut javas = Vec::new();
+    let Ok(extensions) = std::fs::read_dir(root.join("runtime")) else {
+        return javas;
+    };
+    let mut extensions: Vec<PathBuf> = extensions
         .flatten()
         .filter(|entry| {
-            let path = entry.path();
-            // Only consider real directories, not symlinks
-            path.is_dir() && !path.is_symlink() && !should_skip(entry)
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("org.freedesktop.Sdk.Extension.openjdk")
         })
-        .find_map(|entry| {
-            let java_path = entry.path().join("bin/java");
-            if java_path.exists() && java_path.is_file() {
-                Some(java_path)
-            } else {
-                None
+        .map(|entry| entry.path())
+        .collect();
+    extensions.sort();
+
+    for extension in extensions {
+        let Ok(arches) = std::fs::read_dir(&extension) else {
+            continue;
+        };
+        for arch in arches.flatten() {
+            let Ok(branches) = std::fs::read_dir(arch.path()) else {
+                continue;
+            };
+            for branch in branches.flatten() {
+                let files = branch.path().join("active/files");
+                find_java_executables(&files, "java", 2, &mut javas);
             }
-        })
+        }
+    }
+    javas
 }
 
-/// Finds Java installation from SDKMAN! if available
+/// Finds the JDKs installed as snaps under `root`, usually `/snap`
 ///
-/// Scans the SDKMAN! candidates directory and returns the first valid Java installation found.
-/// Skips the 'current' symlink to avoid duplicates.
+/// Path: `<root>/<snap>/current/jdk/bin/java`, e.g. `/snap/openjdk/current/jdk/bin/java`
+#[cfg(any(target_os = "linux", test))]
+fn find_snap_javas(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut javas: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path().join("current/jdk/bin/java"))
+        .filter(|java_path| java_path.is_file())
+        .collect();
+    javas.sort();
+    javas
+}
+
+/// The executable a Scoop or Chocolatey shim runs
 ///
-/// Path: `~/.sdkman/candidates/java/`
+/// Scoop writes a `.shim` file next to the shim, with a line like
+/// `path = "C:\Users\me\scoop\apps\openjdk\current\bin\java.exe"`. Chocolatey shims in
+/// `<chocolatey>\bin` tell their target when run with `--shimgen-noop`, which is given
+/// `WHICH_TIMEOUT` to answer.
+async fn resolve_shim(path: &Path) -> Option<PathBuf> {
+    if let Ok(contents) = tokio::fs::read_to_string(path.with_extension("shim")).await {
+        return parse_shim(&contents);
+    }
+    if cfg!(windows) && path.parent()?.parent()?.join("choco.exe").exists() {
+        // Dropping the child on timeout kills it
+        let mut cmd = tokio::process::Command::new(path);
+        cmd.arg("--shimgen-noop")
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        #[cfg(target_os = "windows")]
+        cmd.creation_flags(0x08000000);
+        let output = tokio::time::timeout(WHICH_TIMEOUT, cmd.output())
+            .await
+            .ok()?
+            .ok()?;
+        return parse_shimgen_noop(&String::from_utf8_lossy(&output.stdout));
+    }
+    None
+}
+
+/// Replace Scoop and Chocolatey shims with the Java they run, keeping the shim as an alias
+/// and merging candidates that turn out to be the same Java. Done while probing rather
+/// than while collecting the candidates, as Chocolatey shims have to be run.
+pub async fn resolve_shims(candidates: Vec<JavaCandidate>) -> Vec<JavaCandidate> {
+    let targets = futures::future::join_all(candidates.iter().map(|c| resolve_shim(&c.path))).await;
+    let mut resolved = CandidateList::default();
+    for (mut candidate, target) in candidates.into_iter().zip(targets) {
+        if let Some(target) = target {
+            let canonical = std::fs::canonicalize(&target)
+                .map(strip_unc_prefix)
+                .unwrap_or(target);
+            if canonical != candidate.path {
+                let shim = std::mem::replace(&mut candidate.path, canonical);
+                candidate.aliases.insert(0, shim);
+            }
+        }
+        resolved.insert(candidate);
+    }
+    resolved.0
+}
+
+/// Reads the target out of the output of a Chocolatey shim run with `--shimgen-noop`, which
+/// has a line like `  path to executable: C:\ProgramData\chocolatey\lib\...\java.exe`
+fn parse_shimgen_noop(output: &str) -> Option<PathBuf> {
+    output.lines().find_map(|line| {
+        let target = line.trim().strip_prefix("path to executable:")?.trim();
+        (!target.is_empty()).then(|| PathBuf::from(target))
+    })
+}
+
+fn parse_shim(contents: &str) -> Option<PathBuf> {
+    contents.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "path").then(|| PathBuf::from(value.trim().trim_matches('"')))
+    })
+}
+
+/// Finds the Java installations recorded in the registry by their installers
 ///
-/// # Returns
-/// `Some(PathBuf)` pointing to `bin/java` if found, `None` otherwise
-pub fn find_sdkman_java() -> Option<PathBuf> {
+/// Both the 64-bit and the 32-bit (WOW6432Node) views are read. Keys that are missing or
+/// can't be read are skipped, leaving the directory scan to find those installations.
+#[cfg(target_os = "windows")]
+fn find_registry_javas() -> Vec<PathBuf> {
+    use winreg::RegKey;
+    use winreg::enums::{HKEY_LOCAL_MACHINE, KEY_READ, KEY_WOW64_32KEY, KEY_WOW64_64KEY};
+
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let mut javas = Vec::new();
+    for view in [KEY_WOW64_64KEY, KEY_WOW64_32KEY] {
+        for (path, value) in REGISTRY_JAVA_KEYS {
+            if let Ok(key) = hklm.open_subkey_with_flags(path, KEY_READ | view) {
+                collect_registry_javas(&key, value, KEY_READ | view, 0, &mut javas);
+            }
+        }
+    }
+    javas
+}
+
+/// Collects the `bin\java.exe` of the Java homes named by `value` in `key` and its subkeys
+#[cfg(target_os = "windows")]
+fn collect_registry_javas(
+    key: &winreg::RegKey,
+    value: &str,
+    flags: u32,
+    depth: usize,
+    javas: &mut Vec<PathBuf>,
+) {
+    if let Ok(home) = key.get_value::<String, _>(value) {
+        let java_path = PathBuf::from(home.trim_end_matches('\\')).join("bin\\java.exe");
+        if java_path.exists() && !javas.contains(&java_path) {
+            javas.push(java_path);
+        }
+    }
+
+    // Adoptium nests the location deepest, as JDK\<version>\hotspot\MSI
+    if depth < 3 {
+        for name in key.enum_keys().flatten() {
+            if let Ok(subkey) = key.open_subkey_with_flags(&name, flags) {
+                collect_registry_javas(&subkey, value, flags, depth + 1, javas);
+            }
+        }
+    }
+}
+
+/// Finds every Java installed with SDKMAN!, with what their directory names tell
+///
+/// Path: `$SDKMAN_DIR/candidates/java/` or `~/.sdkman/candidates/java/`, holding e.g.
+/// `21.0.3-tem` and `8.0.402-amzn`. The `current` link to one of them is skipped, as are
+/// installs that didn't finish and have no `bin/java`.
+#[cfg(unix)]
+fn find_sdkman_javas() -> Vec<(PathBuf, Option<JavaInfo>)> {
+    let sdkman_dir = match std::env::var("SDKMAN_DIR") {
+        Ok(sdkman_dir) => PathBuf::from(sdkman_dir),
+        Err(_) => match std::env::var("HOME") {
+            Ok(home) => PathBuf::from(home).join(".sdkman"),
+            Err(_) => return Vec::new(),
+        },
+    };
+    scan_sdkman_javas(&sdkman_dir)
+}
+
+#[cfg(unix)]
+fn scan_sdkman_javas(sdkman_dir: &Path) -> Vec<(PathBuf, Option<JavaInfo>)> {
+    scan_java_homes(&sdkman_dir.join("candidates/java"), false)
+        .into_iter()
+        .map(|(java_path, name)| {
+            let hint =
+                super::validation::java_info_from_sdkman_name(&java_path.to_string_lossy(), &name);
+            (java_path, hint)
+        })
+        .collect()
+}
+
+/// The asdf data directory, `ASDF_DATA_DIR` or `~/.asdf`
+#[cfg(unix)]
+fn asdf_data_dir() -> Option<PathBuf> {
+    if let Ok(data_dir) = std::env::var("ASDF_DATA_DIR") {
+        return Some(PathBuf::from(data_dir));
+    }
     let home = std::env::var("HOME").ok()?;
-    let sdkman_base = PathBuf::from(&home).join(".sdkman/candidates/java/");
+    Some(PathBuf::from(home).join(".asdf"))
+}
 
-    if !sdkman_base.exists() {
+/// The asdf data directory of `path` if it's an asdf shim
+///
+/// asdf puts scripts running `asdf exec` in `<data dir>/shims`. They can't be used as a
+/// Java path themselves, but they tell where the data directory is.
+fn asdf_root_of_shim(path: &Path) -> Option<PathBuf> {
+    let shims = path.parent()?;
+    if shims.file_name()? != "shims" {
+        return None;
+    }
+    let mut head = Vec::new();
+    std::fs::File::open(path)
+        .ok()?
+        .take(1024)
+        .read_to_end(&mut head)
+        .ok()?;
+    let head = String::from_utf8_lossy(&head);
+    if !head.contains("asdf exec") && !head.contains("asdf-plugin") {
         return None;
     }
+    shims.parent().map(Path::to_path_buf)
+}
 
-    scan_java_dir(&sdkman_base, |entry| entry.file_name() == "current")
+/// Finds the Java installations managed by asdf, with the names of their directories
+///
+/// Path: `<data dir>/installs/java/<name>/`, e.g. `~/.asdf/installs/java/temurin-21.0.2+13.0.LTS`.
+/// macOS builds keep their Java home inside the bundle, under `Contents/Home`.
+#[cfg(unix)]
+fn find_asdf_javas(root: &Path) -> Vec<(PathBuf, String)> {
+    scan_java_homes(&root.join("installs/java"), false)
 }
 
-/// Finds Java installation from mise if available
+/// Finds the Java installations managed by jabba, in `JABBA_HOME` or `~/.jabba`
 ///
-/// Scans the mise Java installation directory and returns the first valid installation found.
-/// Skips version alias symlinks (e.g., `21`, `21.0`, `latest`, `lts`) to avoid duplicates.
+/// Path: `<jabba home>/jdk/<name>/`, e.g. `~/.jabba/jdk/zulu@1.17.0/`
+#[cfg(unix)]
+fn find_jabba_javas() -> Vec<PathBuf> {
+    let Some(jabba_home) = std::env::var("JABBA_HOME")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(std::env::var("HOME").ok()?).join(".jabba")))
+    else {
+        return Vec::new();
+    };
+    // Versions linked to a system JDK are found through their link too
+    scan_java_homes(&jabba_home.join("jdk"), true)
+        .into_iter()
+        .map(|(java_path, _)| java_path)
+        .collect()
+}
+
+/// Finds the Java installations added to jenv, in `JENV_ROOT` or `~/.jenv`
 ///
-/// Path: `~/.local/share/mise/installs/java/`
+/// Path: `<jenv root>/versions/<name>`, links to Java homes elsewhere. A home is usually
+/// linked several times, e.g. as `17`, `17.0` and `openjdk64-17.0.8`.
+#[cfg(unix)]
+fn find_jenv_javas() -> Vec<PathBuf> {
+    let Some(jenv_root) = std::env::var("JENV_ROOT")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(std::env::var("HOME").ok()?).join(".jenv")))
+    else {
+        return Vec::new();
+    };
+    scan_java_homes(&jenv_root.join("versions"), true)
+        .into_iter()
+        .map(|(java_path, _)| java_path)
+        .collect()
+}
+
+/// Lists the Java homes directly in `dir` by their `bin/java`, with their directory names
 ///
-/// # Returns
-/// `Some(PathBuf)` pointing to `bin/java` if found, `None` otherwise
-pub fn find_mise_java() -> Option<PathBuf> {
-    let home = std::env::var("HOME").ok()?;
-    let mise_base = PathBuf::from(&home).join(".local/share/mise/installs/java/");
+/// macOS builds keep their Java home inside the bundle, under `Contents/Home`. Links are only
+/// followed with `follow_links`, and are resolved to the home they point at; broken links
+/// are skipped.
+fn scan_java_homes(dir: &Path, follow_links: bool) -> Vec<(PathBuf, String)> {
+    let bin_name = if cfg!(windows) { "java.exe" } else { "java" };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut javas: Vec<(PathBuf, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let mut home = entry.path();
+            if home.is_symlink() {
+                if !follow_links {
+                    return None;
+                }
+                home = std::fs::canonicalize(&home).ok()?;
+            }
+            let java_path = [
+                home.join("bin").join(bin_name),
+                home.join("Contents/Home/bin").join(bin_name),
+            ]
+            .into_iter()
+            .find(|java_path| java_path.is_file())?;
+            Some((java_path, entry.file_name().to_string_lossy().into_owned()))
+        })
+        .collect();
+    javas.sort();
+    javas
+}
 
-    if !mise_base.exists() {
-        return None;
+/// Finds the JDKs JetBrains IDEs downloaded, with the names of their directories, newest first
+///
+/// Path: `~/.jdks/<name>/`, e.g. `~/.jdks/temurin-21.0.3/` or `~/.jdks/jbr-17.0.9/`, and
+/// `%USERPROFILE%\.jdks` on Windows.
+fn find_jetbrains_javas() -> Vec<(PathBuf, Option<JavaInfo>)> {
+    let home = std::env::var(if cfg!(windows) { "USERPROFILE" } else { "HOME" });
+    let Ok(home) = home else {
+        return Vec::new();
+    };
+    scan_hinted_java_homes(&PathBuf::from(home).join(".jdks"))
+}
+
+/// Lists the Java homes in `dir` with what their directory names tell, newest first
+fn scan_hinted_java_homes(dir: &Path) -> Vec<(PathBuf, Option<JavaInfo>)> {
+    let mut javas: Vec<(PathBuf, Option<JavaInfo>)> = scan_java_homes(dir, false)
+        .into_iter()
+        .map(|(java_path, name)| {
+            let hint =
+                super::validation::java_info_from_dir_name(&java_path.to_string_lossy(), &name);
+            (java_path, hint)
+        })
+        .collect();
+    // Probed in this order, which makes the likeliest picks show up first
+    javas.sort_by_key(|(_, hint)| std::cmp::Reverse(hint.as_ref().map_or(0, |hint| hint.major)));
+    javas
+}
+
+/// Finds the Java homes registered with macOS, as listed by `/usr/libexec/java_home -V`
+///
+/// This includes JVMs installed outside `/Library/Java/JavaVirtualMachines`. Without any
+/// registered JVM the tool fails, offering to install one, which just gives no candidates.
+#[cfg(target_os = "macos")]
+fn find_registered_macos_javas() -> Vec<PathBuf> {
+    let mut cmd = Command::new("/usr/libexec/java_home");
+    cmd.arg("-V");
+    let Some((_, table)) = run_command_with_timeout(cmd) else {
+        return Vec::new();
+    };
+    parse_java_home_table(&table)
+        .into_iter()
+        .map(|home| home.join("bin/java"))
+        .filter(|java_path| java_path.exists())
+        .collect()
+}
+
+/// Reads the Java homes out of the table `java_home -V` prints to stderr, whose rows look like
+/// `    21.0.2 (arm64) "Eclipse Adoptium" - "OpenJDK 21.0.2" /Library/Java/.../Contents/Home`
+#[cfg(any(target_os = "macos", test))]
+fn parse_java_home_table(table: &str) -> Vec<PathBuf> {
+    table
+        .lines()
+        .filter(|line| line.starts_with(char::is_whitespace))
+        .filter_map(|line| {
+            // The path comes after the quoted vendor and name, and may contain spaces
+            let rest = line.rfind('"').map_or(line, |quote| &line[quote + 1..]);
+            let path = rest[rest.find('/')?..].trim();
+            Some(PathBuf::from(path))
+        })
+        .collect()
+}
+
+/// Lists the `java` of the standard Nix profiles that have one
+///
+/// Only the profiles' `bin` directories are looked at; their links lead into `/nix/store`,
+/// which is far too big to scan itself.
+#[cfg(unix)]
+fn find_nix_profile_javas() -> Vec<PathBuf> {
+    let mut profiles = vec![
+        PathBuf::from("/run/current-system/sw"),
+        PathBuf::from("/nix/var/nix/profiles/default"),
+    ];
+    if let Ok(user) = std::env::var("USER") {
+        // Installed by home-manager or `users.users.<name>.packages`
+        profiles.push(PathBuf::from("/etc/profiles/per-user").join(user));
+    }
+    if let Ok(home) = std::env::var("HOME") {
+        let home = PathBuf::from(home);
+        let state_home = std::env::var("XDG_STATE_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| home.join(".local/state"));
+        profiles.push(home.join(".nix-profile"));
+        profiles.push(state_home.join("nix/profiles/profile"));
+    }
+
+    profiles
+        .into_iter()
+        .map(|profile| profile.join("bin/java"))
+        .filter(|java_path| java_path.exists())
+        .collect()
+}
+
+/// The name of the Nix package `path` is in, e.g. "openjdk-21.0.2+13" for
+/// `/nix/store/<hash>-openjdk-21.0.2+13/lib/openjdk/bin/java`
+#[cfg(unix)]
+fn nix_store_package(path: &Path) -> Option<String> {
+    let mut components = path.components().map(|c| c.as_os_str().to_string_lossy());
+    while let Some(component) = components.next() {
+        if component == "nix" && components.next()? == "store" {
+            let entry = components.next()?;
+            let (_hash, name) = entry.split_once('-')?;
+            return Some(name.to_string());
+        }
     }
+    None
+}
 
-    scan_java_dir(&mise_base, |_| false) // mise: no additional filtering needed
+/// The mise data directory, `MISE_DATA_DIR`, `$XDG_DATA_HOME/mise` or `~/.local/share/mise`
+#[cfg(unix)]
+fn mise_data_dir() -> Option<PathBuf> {
+    if let Ok(data_dir) = std::env::var("MISE_DATA_DIR") {
+        return Some(PathBuf::from(data_dir));
+    }
+    if let Ok(data_home) = std::env::var("XDG_DATA_HOME") {
+        return Some(PathBuf::from(data_home).join("mise"));
+    }
+    let home = std::env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".local/share/mise"))
+}
+
+/// Finds the Java installations managed by mise, with the names of their directories
+///
+/// Path: `<data dir>/installs/java/<version>/`. mise also links aliases like `21`, `latest`
+/// and `lts` to the installed versions there; those are left to the candidate merging, which
+/// keeps one entry per Java home. Installed versions come first, so their names are the
+/// ones kept.
+#[cfg(unix)]
+fn find_mise_javas(root: &Path) -> Vec<(PathBuf, String)> {
+    let installs = root.join("installs/java");
+    let mut javas = scan_java_homes(&installs, false);
+    javas.extend(scan_java_homes(&installs, true));
+    javas
+}
+
+/// Lists the `java` (`java.exe` on Windows) of every directory in `path_var`, in order
+///
+/// Empty and relative entries are skipped, as they'd depend on the launcher's working directory.
+fn find_path_javas(path_var: &std::ffi::OsStr) -> Vec<PathBuf> {
+    let bin_name = if cfg!(windows) { "java.exe" } else { "java" };
+    let mut javas: Vec<PathBuf> = Vec::new();
+    for dir in std::env::split_paths(path_var) {
+        if dir.as_os_str().is_empty() || dir.is_relative() {
+            continue;
+        }
+        let java_path = dir.join(bin_name);
+        if java_path.is_file() && !javas.contains(&java_path) {
+            javas.push(java_path);
+        }
+    }
+    javas
 }
 
 /// Runs `which` (Unix) or `where` (Windows) command to find Java in PATH with timeout
@@ -101,15 +600,24 @@ pub fn find_mise_java() -> Option<PathBuf> {
 fn run_which_command_with_timeout() -> Option<String> {
     let mut cmd = Command::new(if cfg!(windows) { "where" } else { "which" });
     cmd.arg("java");
+    run_command_with_timeout(cmd).map(|(stdout, _)| stdout)
+}
+
+/// Runs `cmd` and returns its stdout and stderr, or `None` if it fails or takes longer than
+/// `WHICH_TIMEOUT`, in which case it's killed
+fn run_command_with_timeout(mut cmd: Command) -> Option<(String, String)> {
     // Hide console window on Windows
     #[cfg(target_os = "windows")]
     cmd.creation_flags(0x08000000);
-    cmd.stdout(Stdio::piped());
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
     let mut child = cmd.spawn().ok()?;
+    // Both pipes are read while the command runs, so it can't block on a full one
+    let stdout = child.stdout.take().map(read_pipe);
+    let stderr = child.stderr.take().map(read_pipe);
     let start = std::time::Instant::now();
 
-    loop {
+    let status = loop {
         // Check if timeout has been exceeded
         if start.elapsed() > WHICH_TIMEOUT {
             let _ = child.kill();
@@ -118,18 +626,7 @@ fn run_which_command_with_timeout() -> Option<String> {
         }
 
         match child.try_wait() {
-            Ok(Some(status)) => {
-                if status.success() {
-                    let mut output = String::new();
-                    if let Some(mut stdout) = child.stdout.take() {
-                        let _ = stdout.read_to_string(&mut output);
-                    }
-                    return Some(output);
-                } else {
-                    let _ = child.wait();
-                    return None;
-                }
-            }
+            Ok(Some(status)) => break status,
             Ok(None) => {
                 // Command still running, sleep briefly before checking again
                 std::thread::sleep(Duration::from_millis(50));
@@ -140,43 +637,138 @@ fn run_which_command_with_timeout() -> Option<String> {
                 return None;
             }
         }
+    };
+    if !status.success() {
+        return None;
+    }
+    let collect = |reader: Option<std::thread::JoinHandle<String>>| {
+        reader
+            .and_then(|reader| reader.join().ok())
+            .unwrap_or_default()
+    };
+    Some((collect(stdout), collect(stderr)))
+}
+
+/// Read `pipe` to the end on a thread of its own
+fn read_pipe(mut pipe: impl Read + Send + 'static) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut output = String::new();
+        let _ = pipe.read_to_string(&mut output);
+        output
+    })
+}
+
+/// A Java executable found on the system
+#[derive(Debug, Clone, PartialEq)]
+pub struct JavaCandidate {
+    /// Canonical path of the executable
+    pub path: PathBuf,
+    /// Where it was found, e.g. "PATH" or "JAVA_HOME", in the order it was found there
+    pub sources: Vec<String>,
+    /// The other paths it was found as, e.g. `/usr/bin/java` for a linked executable
+    pub aliases: Vec<PathBuf>,
+    /// What its location tells about it, e.g. the version in an asdf directory name. Filled
+    /// in with what probing finds out.
+    pub hint: Option<JavaInfo>,
+}
+
+/// Candidates found so far, each executable once
+#[derive(Debug, Default)]
+struct CandidateList(Vec<JavaCandidate>);
+
+impl CandidateList {
+    /// Add a Java executable found via `source`, merging it into the candidate it links to
+    fn add(&mut self, path: PathBuf, source: impl AsRef<str>) {
+        self.add_with_hint(path, source, None);
+    }
+
+    fn add_with_hint(&mut self, path: PathBuf, source: impl AsRef<str>, hint: Option<JavaInfo>) {
+        // Linux `which` and Java directories often give links, e.g. `/etc/alternatives/java`,
+        // which some mod loaders break on. Shims are resolved by `resolve_shims`.
+        let canonical = std::fs::canonicalize(&path)
+            .map(strip_unc_prefix)
+            .unwrap_or_else(|_| path.clone());
+        self.insert(JavaCandidate {
+            aliases: if path == canonical {
+                Vec::new()
+            } else {
+                vec![path]
+            },
+            path: canonical,
+            sources: vec![source.as_ref().to_string()],
+            hint,
+        });
+    }
+
+    /// Add `new`, or merge its sources, aliases and hint into the candidate with its path
+    fn insert(&mut self, new: JavaCandidate) {
+        let Some(candidate) = self.0.iter_mut().find(|c| c.path == new.path) else {
+            self.0.push(new);
+            return;
+        };
+        if candidate.hint.is_none() {
+            candidate.hint = new.hint;
+        }
+        for source in new.sources {
+            if !candidate.sources.contains(&source) {
+                candidate.sources.push(source);
+            }
+        }
+        for alias in new.aliases {
+            if alias != candidate.path && !candidate.aliases.contains(&alias) {
+                candidate.aliases.push(alias);
+            }
+        }
     }
 }
 
 /// Detects all available Java installations on the system
 ///
 /// This function searches for Java installations in multiple locations:
-/// - **All platforms**: `JAVA_HOME` environment variable, `java` in PATH
-/// - **Linux**: `/usr/lib/jvm`, `/usr/java`, `/opt/java`, `/opt/jdk`, `/opt/openjdk`, SDKMAN!
+/// - **All platforms**: `JAVA_HOME` environment variable, `java` in PATH, JetBrains `~/.jdks`
+/// - **Linux**: `/usr/lib/jvm`, `/usr/java`, `/opt/java`, `/opt/jdk`, `/opt/openjdk`, SDKMAN!,
+///   Flatpak, Snap
 /// - **macOS**: `/Library/Java/JavaVirtualMachines`, `/System/Library/Java/JavaVirtualMachines`,
 ///   Homebrew paths (`/usr/local/opt/openjdk`, `/opt/homebrew/opt/openjdk`), SDKMAN!
-/// - **Windows**: `Program Files`, `Program Files (x86)`, `LOCALAPPDATA` for various JDK distributions
+/// - **Linux and macOS**: asdf, mise, jabba, jenv and Nix profiles
+/// - **Windows**: `Program Files`, `Program Files (x86)`, `LOCALAPPDATA` for various JDK distributions,
+///   the registry, Scoop, Chocolatey
 ///
 /// # Returns
-/// A vector of `PathBuf` pointing to Java executables found on the system.
-/// Note: Paths may include symlinks and duplicates; callers should canonicalize and deduplicate as needed.
+/// The Java executables found on the system, in the order they were first found. Each
+/// appears once, by its canonical path, with every place that led to it.
 ///
 /// # Examples
 /// ```ignore
 /// let candidates = get_java_candidates();
-/// for java_path in candidates {
-///     println!("Found Java at: {}", java_path.display());
+/// for candidate in candidates {
+///     println!("Found Java at: {} via {}", candidate.path.display(), candidate.sources.join(", "));
 /// }
 /// ```
-pub fn get_java_candidates() -> Vec<PathBuf> {
-    let mut candidates = Vec::new();
-
-    // Try to find Java in PATH using 'which' or 'where' command with timeout
-    // CAUTION: linux 'which' may return symlinks, so we need to canonicalize later
-    if let Some(paths_str) = run_which_command_with_timeout() {
-        for line in paths_str.lines() {
-            let path = PathBuf::from(line.trim());
-            if path.exists() {
-                let resolved = std::fs::canonicalize(&path).unwrap_or(path);
-                let final_path = strip_unc_prefix(resolved);
-                candidates.push(final_path);
-            }
+pub fn get_java_candidates() -> Vec<JavaCandidate> {
+    let mut candidates = CandidateList::default();
+
+    // Look for Java in every PATH directory, falling back to 'which' or 'where' with a
+    // timeout when PATH gives none
+    let mut path_javas = std::env::var_os("PATH")
+        .map(|path_var| find_path_javas(&path_var))
+        .unwrap_or_default();
+    if path_javas.is_empty()
+        && let Some(paths_str) = run_which_command_with_timeout()
+    {
+        path_javas = paths_str
+            .lines()
+            .map(|line| PathBuf::from(line.trim()))
+            .filter(|path| path.exists())
+            .collect();
+    }
+    let mut asdf_roots: Vec<PathBuf> = Vec::new();
+    for path in path_javas {
+        if let Some(root) = asdf_root_of_shim(&path) {
+            asdf_roots.push(root);
+            continue;
         }
+        candidates.add(path, "PATH");
     }
 
     #[cfg(target_os = "linux")]
@@ -194,20 +786,29 @@ pub fn get_java_candidates() -> Vec<PathBuf> {
                 for entry in entries.flatten() {
                     let java_path = entry.path().join("bin/java");
                     if java_path.exists() {
-                        candidates.push(java_path);
+                        candidates.add(java_path, base);
                     }
                 }
             }
         }
 
         // Check common SDKMAN! java candidates
-        if let Some(sdkman_java) = find_sdkman_java() {
-            candidates.push(sdkman_java);
+        for (java_path, hint) in find_sdkman_javas() {
+            candidates.add_with_hint(java_path, "SDKMAN!", hint);
         }
 
-        // Check common mise java candidates
-        if let Some(mise_java) = find_mise_java() {
-            candidates.push(mise_java);
+        // Immutable distributions like SteamOS may only have Java from Flatpak or Snap
+        let mut flatpak_roots = vec![PathBuf::from("/var/lib/flatpak")];
+        if let Ok(home) = std::env::var("HOME") {
+            flatpak_roots.push(PathBuf::from(home).join(".local/share/flatpak"));
+        }
+        for root in &flatpak_roots {
+            for java_path in find_flatpak_javas(root) {
+                candidates.add(java_path, "Flatpak");
+            }
+        }
+        for java_path in find_snap_javas(Path::new("/snap")) {
+            candidates.add(java_path, "Snap");
         }
     }
 
@@ -227,15 +828,20 @@ pub fn get_java_candidates() -> Vec<PathBuf> {
                     for entry in entries.flatten() {
                         let java_path = entry.path().join("Contents/Home/bin/java");
                         if java_path.exists() {
-                            candidates.push(java_path);
+                            candidates.add(java_path, path);
                         }
                     }
                 }
             } else if p.exists() {
-                candidates.push(p);
+                candidates.add(p, "Homebrew");
             }
         }
 
+        // JVMs registered with macOS, wherever they are installed
+        for java_path in find_registered_macos_javas() {
+            candidates.add(java_path, "java_home");
+        }
+
         // Check common Homebrew java candidates for aarch64 macs
         let homebrew_arm = PathBuf::from("/opt/homebrew/Cellar/openjdk");
         if homebrew_arm.exists() {
@@ -245,20 +851,15 @@ pub fn get_java_candidates() -> Vec<PathBuf> {
                         .path()
                         .join("libexec/openjdk.jdk/Contents/Home/bin/java");
                     if java_path.exists() {
-                        candidates.push(java_path);
+                        candidates.add(java_path, "Homebrew");
                     }
                 }
             }
         }
 
         // Check common SDKMAN! java candidates
-        if let Some(sdkman_java) = find_sdkman_java() {
-            candidates.push(sdkman_java);
-        }
-
-        // Check common mise java candidates
-        if let Some(mise_java) = find_mise_java() {
-            candidates.push(mise_java);
+        for (java_path, hint) in find_sdkman_javas() {
+            candidates.add_with_hint(java_path, "SDKMAN!", hint);
         }
     }
 
@@ -290,12 +891,69 @@ pub fn get_java_candidates() -> Vec<PathBuf> {
                     for entry in entries.flatten() {
                         let java_path = entry.path().join("bin\\java.exe");
                         if java_path.exists() {
-                            candidates.push(java_path);
+                            candidates.add(java_path, base);
                         }
                     }
                 }
             }
         }
+
+        // Installations outside the usual directories are only known to the registry
+        for java_path in find_registry_javas() {
+            candidates.add(java_path, "Registry");
+        }
+
+        for java_path in find_scoop_javas() {
+            candidates.add(java_path, "Scoop");
+        }
+
+        for java_path in find_chocolatey_javas() {
+            candidates.add(java_path, "Chocolatey");
+        }
+    }
+
+    // asdf keeps its Java versions out of PATH, behind a shim, and mise, jabba and jenv
+    // switch between theirs by changing JAVA_HOME or PATH
+    #[cfg(unix)]
+    {
+        asdf_roots.extend(asdf_data_dir());
+        asdf_roots.dedup();
+        for root in &asdf_roots {
+            for (java_path, name) in find_asdf_javas(root) {
+                let hint =
+                    super::validation::java_info_from_dir_name(&java_path.to_string_lossy(), &name);
+                candidates.add_with_hint(java_path, "asdf", hint);
+            }
+        }
+
+        // Profiles link into the store, whose package names tell the version
+        for java_path in find_nix_profile_javas() {
+            let hint = std::fs::canonicalize(&java_path).ok().and_then(|target| {
+                let package = nix_store_package(&target)?;
+                super::validation::java_info_from_dir_name(&target.to_string_lossy(), &package)
+            });
+            candidates.add_with_hint(java_path, "Nix", hint);
+        }
+
+        for (java_path, name) in
+            mise_data_dir().map_or_else(Vec::new, |root| find_mise_javas(&root))
+        {
+            let hint =
+                super::validation::java_info_from_dir_name(&java_path.to_string_lossy(), &name);
+            candidates.add_with_hint(java_path, "Mise", hint);
+        }
+
+        for java_path in find_jabba_javas() {
+            candidates.add(java_path, "jabba");
+        }
+        for java_path in find_jenv_javas() {
+            candidates.add(java_path, "jenv");
+        }
+    }
+
+    // Downloaded from IntelliJ IDEA and the other JetBrains IDEs
+    for (java_path, hint) in find_jetbrains_javas() {
+        candidates.add_with_hint(java_path, "JetBrains", hint);
     }
 
     // Check JAVA_HOME environment variable
@@ -303,9 +961,457 @@ pub fn get_java_candidates() -> Vec<PathBuf> {
         let bin_name = if cfg!(windows) { "java.exe" } else { "java" };
         let java_path = PathBuf::from(&java_home).join("bin").join(bin_name);
         if java_path.exists() {
-            candidates.push(java_path);
+            candidates.add(java_path, "JAVA_HOME");
+        }
+    }
+
+    candidates.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_command_with_timeout_drains_both_pipes() {
+        // More than a pipe buffer holds, the command only exits once it's all read
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "head -c 200000 /dev/zero | tr '\\0' x >&2; echo done"]);
+        let (stdout, stderr) = run_command_with_timeout(cmd).unwrap();
+        assert_eq!(stdout, "done\n");
+        assert_eq!(stderr.len(), 200000);
+    }
+
+    #[test]
+    fn test_parse_shim() {
+        let shim = "path = \"C:\\Users\\me\\scoop\\apps\\temurin17-jdk\\current\\bin\\java.exe\"\nargs = -Xshare:auto\n";
+        assert_eq!(
+            parse_shim(shim),
+            Some(PathBuf::from(
+                "C:\\Users\\me\\scoop\\apps\\temurin17-jdk\\current\\bin\\java.exe"
+            ))
+        );
+        // Older Scoop versions leave out the quotes
+        assert_eq!(
+            parse_shim("path = C:\\scoop\\apps\\openjdk\\current\\bin\\java.exe"),
+            Some(PathBuf::from(
+                "C:\\scoop\\apps\\openjdk\\current\\bin\\java.exe"
+            ))
+        );
+        assert_eq!(parse_shim("args = -version"), None);
+    }
+
+    #[test]
+    fn test_find_path_javas_checks_every_entry() {
+        let dir = std::env::temp_dir().join(format!("dropout-path-{}", uuid::Uuid::new_v4()));
+        let bin_name = if cfg!(windows) { "java.exe" } else { "java" };
+        for jdk in ["jdk-17/bin", "jdk-21/bin", "empty/bin"] {
+            std::fs::create_dir_all(dir.join(jdk)).unwrap();
+        }
+        std::fs::write(dir.join("jdk-17/bin").join(bin_name), b"").unwrap();
+        std::fs::write(dir.join("jdk-21/bin").join(bin_name), b"").unwrap();
+
+        let path_var = std::env::join_paths([
+            dir.join("jdk-21/bin"),
+            PathBuf::new(),
+            dir.join("empty/bin"),
+            PathBuf::from("jdk-17/bin"),
+            dir.join("missing/bin"),
+            dir.join("jdk-17/bin"),
+            dir.join("jdk-21/bin"),
+        ])
+        .unwrap();
+        assert_eq!(
+            find_path_javas(&path_var),
+            vec![
+                dir.join("jdk-21/bin").join(bin_name),
+                dir.join("jdk-17/bin").join(bin_name),
+            ]
+        );
+        assert!(find_path_javas(std::ffi::OsStr::new("")).is_empty());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_parse_java_home_table() {
+        let table = "Matching Java Virtual Machines (3):
+    21.0.2 (arm64) \"Eclipse Adoptium\" - \"OpenJDK 21.0.2\" /Library/Java/JavaVirtualMachines/temurin-21.jdk/Contents/Home
+    17.0.8 (x86_64) \"Amazon.com Inc.\" - \"Amazon Corretto 17\" /Users/me/Library/Application Support/JDKs/corretto-17.0.8/Contents/Home
+    1.8.0_292, x86_64:\t\"AdoptOpenJDK 8\"\t/opt/jdks/adoptopenjdk-8.jdk/Contents/Home
+/Library/Java/JavaVirtualMachines/temurin-21.jdk/Contents/Home
+";
+        assert_eq!(
+            parse_java_home_table(table),
+            vec![
+                PathBuf::from("/Library/Java/JavaVirtualMachines/temurin-21.jdk/Contents/Home"),
+                PathBuf::from(
+                    "/Users/me/Library/Application Support/JDKs/corretto-17.0.8/Contents/Home"
+                ),
+                PathBuf::from("/opt/jdks/adoptopenjdk-8.jdk/Contents/Home"),
+            ]
+        );
+        // What it prints when no JVM is registered
+        assert!(
+            parse_java_home_table(
+                "The operation couldn’t be completed. Unable to locate a Java Runtime.\n"
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_parse_shimgen_noop() {
+        let output = "[ShimGen]: Shim Noop (--shimgen-noop) detected. Shim will not execute.\n  path to executable: C:\\ProgramData\\chocolatey\\lib\\temurin17\\tools\\jdk-17.0.8+7\\bin\\java.exe\n  working directory: C:\\Users\\me\n";
+        assert_eq!(
+            parse_shimgen_noop(output),
+            Some(PathBuf::from(
+                "C:\\ProgramData\\chocolatey\\lib\\temurin17\\tools\\jdk-17.0.8+7\\bin\\java.exe"
+            ))
+        );
+        assert_eq!(parse_shimgen_noop("openjdk version \"17.0.8\""), None);
+    }
+
+    #[test]
+    fn test_find_java_executables_stops_at_depth() {
+        let lib = std::env::temp_dir().join(format!("dropout-choco-{}", uuid::Uuid::new_v4()));
+        let shallow = lib.join("openjdk/tools/jdk-17.0.8/bin");
+        let deep = lib.join("nested/a/b/c/d/jdk/bin");
+        std::fs::create_dir_all(&shallow).unwrap();
+        std::fs::create_dir_all(&deep).unwrap();
+        std::fs::write(shallow.join("java.exe"), b"").unwrap();
+        std::fs::write(deep.join("java.exe"), b"").unwrap();
+
+        let mut javas = Vec::new();
+        for package in ["nested", "openjdk"] {
+            find_java_executables(
+                &lib.join(package),
+                "java.exe",
+                CHOCOLATEY_SCAN_DEPTH,
+                &mut javas,
+            );
+        }
+        assert_eq!(javas, vec![shallow.join("java.exe")]);
+        std::fs::remove_dir_all(&lib).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_asdf_javas_behind_shim() {
+        let root = std::env::temp_dir().join(format!("dropout-asdf-{}", uuid::Uuid::new_v4()));
+        let installs = root.join("installs/java");
+        std::fs::create_dir_all(installs.join("temurin-21.0.2+13.0.LTS/bin")).unwrap();
+        std::fs::create_dir_all(installs.join("zulu-17.46.19/Contents/Home/bin")).unwrap();
+        std::fs::create_dir_all(installs.join("corrupted-11")).unwrap();
+        std::fs::create_dir_all(root.join("shims")).unwrap();
+        std::fs::write(installs.join("temurin-21.0.2+13.0.LTS/bin/java"), b"").unwrap();
+        std::fs::write(installs.join("zulu-17.46.19/Contents/Home/bin/java"), b"").unwrap();
+        std::fs::write(
+            root.join("shims/java"),
+            "#!/usr/bin/env bash\n# asdf-plugin: java 21.0.2\nexec asdf exec \"java\" \"$@\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            asdf_root_of_shim(&root.join("shims/java")),
+            Some(root.clone())
+        );
+        assert_eq!(
+            asdf_root_of_shim(&installs.join("temurin-21.0.2+13.0.LTS/bin/java")),
+            None
+        );
+        assert_eq!(
+            find_asdf_javas(&root),
+            vec![
+                (
+                    installs.join("temurin-21.0.2+13.0.LTS/bin/java"),
+                    "temurin-21.0.2+13.0.LTS".to_string()
+                ),
+                (
+                    installs.join("zulu-17.46.19/Contents/Home/bin/java"),
+                    "zulu-17.46.19".to_string()
+                ),
+            ]
+        );
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_java_homes_resolves_links() {
+        let dir = std::env::temp_dir().join(format!("dropout-jenv-{}", uuid::Uuid::new_v4()));
+        let versions = dir.join("versions");
+        let home = dir.join("jdk-17.0.8");
+        let bundle = dir.join("zulu-21.jdk/Contents/Home");
+        std::fs::create_dir_all(&versions).unwrap();
+        std::fs::create_dir_all(home.join("bin")).unwrap();
+        std::fs::create_dir_all(bundle.join("bin")).unwrap();
+        let dir = std::fs::canonicalize(&dir).unwrap();
+        let (versions, home) = (dir.join("versions"), dir.join("jdk-17.0.8"));
+        std::fs::write(home.join("bin/java"), b"").unwrap();
+        std::fs::write(dir.join("zulu-21.jdk/Contents/Home/bin/java"), b"").unwrap();
+        std::os::unix::fs::symlink(&home, versions.join("17")).unwrap();
+        std::os::unix::fs::symlink(&home, versions.join("openjdk64-17.0.8")).unwrap();
+        std::os::unix::fs::symlink(dir.join("zulu-21.jdk"), versions.join("21")).unwrap();
+        std::os::unix::fs::symlink(dir.join("removed"), versions.join("11")).unwrap();
+
+        let found = scan_java_homes(&versions, true);
+        assert_eq!(
+            found,
+            vec![
+                (home.join("bin/java"), "17".to_string()),
+                (home.join("bin/java"), "openjdk64-17.0.8".to_string()),
+                (
+                    dir.join("zulu-21.jdk/Contents/Home/bin/java"),
+                    "21".to_string()
+                ),
+            ]
+        );
+        assert!(scan_java_homes(&versions, false).is_empty());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_sdkman_javas_lists_every_version() {
+        let root = std::env::temp_dir().join(format!("dropout-sdkman-{}", uuid::Uuid::new_v4()));
+        let java = root.join("candidates/java");
+        std::fs::create_dir_all(java.join("21.0.3-tem/bin")).unwrap();
+        std::fs::create_dir_all(java.join("8.0.402-amzn/Contents/Home/bin")).unwrap();
+        // Left behind by an interrupted install
+        std::fs::create_dir_all(java.join("17.0.9-zulu")).unwrap();
+        std::fs::write(java.join("21.0.3-tem/bin/java"), b"").unwrap();
+        std::fs::write(java.join("8.0.402-amzn/Contents/Home/bin/java"), b"").unwrap();
+        std::os::unix::fs::symlink(java.join("21.0.3-tem"), java.join("current")).unwrap();
+
+        let found: Vec<(PathBuf, Option<(u32, String)>)> = scan_sdkman_javas(&root)
+            .into_iter()
+            .map(|(java_path, hint)| (java_path, hint.map(|hint| (hint.major, hint.vendor))))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    java.join("21.0.3-tem/bin/java"),
+                    Some((21, "Temurin (Eclipse)".to_string()))
+                ),
+                (
+                    java.join("8.0.402-amzn/Contents/Home/bin/java"),
+                    Some((8, "Corretto (Amazon)".to_string()))
+                ),
+            ]
+        );
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_mise_javas_skips_aliases() {
+        let root = std::env::temp_dir().join(format!("dropout-mise-{}", uuid::Uuid::new_v4()));
+        let installs = root.join("installs/java");
+        std::fs::create_dir_all(installs.join("21.0.2/bin")).unwrap();
+        std::fs::create_dir_all(installs.join("temurin-17.0.8+7/Contents/Home/bin")).unwrap();
+        let root = std::fs::canonicalize(&root).unwrap();
+        let installs = root.join("installs/java");
+        let java21 = installs.join("21.0.2/bin/java");
+        let java17 = installs.join("temurin-17.0.8+7/Contents/Home/bin/java");
+        std::fs::write(&java21, b"").unwrap();
+        std::fs::write(&java17, b"").unwrap();
+        // Aliases mise links to the installed versions
+        std::os::unix::fs::symlink(installs.join("21.0.2"), installs.join("21")).unwrap();
+        std::os::unix::fs::symlink(installs.join("21.0.2"), installs.join("latest")).unwrap();
+        std::os::unix::fs::symlink("./temurin-17.0.8+7", installs.join("lts")).unwrap();
+
+        let mut candidates = CandidateList::default();
+        for (java_path, name) in find_mise_javas(&root) {
+            let hint = crate::core::java::validation::java_info_from_dir_name(
+                &java_path.to_string_lossy(),
+                &name,
+            );
+            candidates.add_with_hint(java_path, "Mise", hint);
         }
+
+        let found: Vec<_> = candidates.0.iter().map(|c| c.path.clone()).collect();
+        assert_eq!(found, vec![java21, java17]);
+        assert!(candidates.0.iter().all(|c| c.sources == vec!["Mise"]));
+        assert!(candidates.0.iter().all(|c| c.aliases.is_empty()));
+        let hint = candidates.0[1].hint.as_ref().unwrap();
+        assert_eq!(
+            (hint.major, hint.vendor.as_str()),
+            (17, "Temurin (Eclipse)")
+        );
+        std::fs::remove_dir_all(&root).ok();
     }
 
-    candidates
+    #[cfg(unix)]
+    #[test]
+    fn test_nix_store_package() {
+        assert_eq!(
+            nix_store_package(Path::new(
+                "/nix/store/0c5mx2a9rvyx1h1bbc2jfm1fk6vw9zaz-openjdk-21.0.2+13/lib/openjdk/bin/java"
+            )),
+            Some("openjdk-21.0.2+13".to_string())
+        );
+        assert_eq!(
+            nix_store_package(Path::new("/home/me/.nix-profile/bin/java")),
+            None
+        );
+        assert_eq!(
+            nix_store_package(Path::new("/usr/lib/jvm/nix/bin/java")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_flatpak_and_snap_javas() {
+        let dir = std::env::temp_dir().join(format!("dropout-sandboxed-{}", uuid::Uuid::new_v4()));
+        let flatpak = dir.join("flatpak");
+        let extension = flatpak.join("runtime/org.freedesktop.Sdk.Extension.openjdk17");
+        let jdk = extension.join("x86_64/23.08/active/files/jvm/openjdk-17");
+        std::fs::create_dir_all(jdk.join("bin")).unwrap();
+        std::fs::create_dir_all(
+            flatpak.join("runtime/org.freedesktop.Platform/x86_64/23.08/active/files/bin"),
+        )
+        .unwrap();
+        std::fs::write(jdk.join("bin/java"), b"").unwrap();
+        std::fs::write(
+            flatpak.join("runtime/org.freedesktop.Platform/x86_64/23.08/active/files/bin/java"),
+            b"",
+        )
+        .unwrap();
+        let snap = dir.join("snap");
+        std::fs::create_dir_all(snap.join("openjdk/current/jdk/bin")).unwrap();
+        std::fs::create_dir_all(snap.join("firefox/current")).unwrap();
+        std::fs::write(snap.join("openjdk/current/jdk/bin/java"), b"").unwrap();
+
+        assert_eq!(find_flatpak_javas(&flatpak), vec![jdk.join("bin/java")]);
+        assert_eq!(
+            find_snap_javas(&snap),
+            vec![snap.join("openjdk/current/jdk/bin/java")]
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_scan_hinted_java_homes_sorts_newest_first() {
+        let jdks = std::env::temp_dir().join(format!("dropout-jdks-{}", uuid::Uuid::new_v4()));
+        let bin_name = if cfg!(windows) { "java.exe" } else { "java" };
+        let homes = [
+            "corretto-11.0.21/bin",
+            "temurin-21.0.3/Contents/Home/bin",
+            "jbr-17.0.9/bin",
+            "custom/bin",
+        ];
+        for home in homes {
+            std::fs::create_dir_all(jdks.join(home)).unwrap();
+            std::fs::write(jdks.join(home).join(bin_name), b"").unwrap();
+        }
+
+        let found: Vec<(PathBuf, Option<String>)> = scan_hinted_java_homes(&jdks)
+            .into_iter()
+            .map(|(java_path, hint)| (java_path, hint.map(|hint| hint.vendor)))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    jdks.join(homes[1]).join(bin_name),
+                    Some("Temurin (Eclipse)".to_string())
+                ),
+                (
+                    jdks.join(homes[2]).join(bin_name),
+                    Some("JetBrains Runtime".to_string())
+                ),
+                (
+                    jdks.join(homes[0]).join(bin_name),
+                    Some("Corretto (Amazon)".to_string())
+                ),
+                (jdks.join(homes[3]).join(bin_name), None),
+            ]
+        );
+        std::fs::remove_dir_all(&jdks).ok();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_scoop_javas_resolve_shims_and_current_links() {
+        let root = std::env::temp_dir().join(format!("dropout-scoop-{}", uuid::Uuid::new_v4()));
+        let app = root.join("apps/temurin17-jdk");
+        std::fs::create_dir_all(app.join("17.0.8/bin")).unwrap();
+        std::fs::create_dir_all(root.join("apps/git/current/bin")).unwrap();
+        std::fs::create_dir_all(root.join("shims")).unwrap();
+        let root = std::fs::canonicalize(&root).unwrap();
+        let app = root.join("apps/temurin17-jdk");
+        std::fs::write(app.join("17.0.8/bin/java.exe"), b"").unwrap();
+        std::os::unix::fs::symlink(app.join("17.0.8"), app.join("current")).unwrap();
+        let current = app.join("current/bin/java.exe");
+        std::fs::write(root.join("shims/java.exe"), b"").unwrap();
+        std::fs::write(
+            root.join("shims/java.shim"),
+            format!("path = \"{}\"\n", current.display()),
+        )
+        .unwrap();
+
+        assert_eq!(scan_scoop_apps(&root), vec![current.clone()]);
+
+        let mut candidates = CandidateList::default();
+        candidates.add(root.join("shims/java.exe"), "PATH");
+        candidates.add(current.clone(), "Scoop");
+        // The shim isn't run or read until the candidates are probed
+        assert_eq!(candidates.0.len(), 2);
+        let candidates = CandidateList(resolve_shims(candidates.0).await);
+        assert_eq!(candidates.0.len(), 1);
+        assert_eq!(candidates.0[0].path, app.join("17.0.8/bin/java.exe"));
+        assert_eq!(candidates.0[0].sources, vec!["PATH", "Scoop"]);
+        assert_eq!(
+            candidates.0[0].aliases,
+            vec![root.join("shims/java.exe"), current]
+        );
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_candidate_list_merges_links_to_the_same_java() {
+        let dir = std::env::temp_dir().join(format!("dropout-candidates-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // The temporary directory itself may be behind a link, e.g. on macOS
+        let dir = std::fs::canonicalize(&dir).unwrap();
+        let home = dir.join("jdk-17");
+        std::fs::create_dir_all(home.join("bin")).unwrap();
+        std::fs::write(home.join("bin/java"), b"").unwrap();
+        std::fs::write(dir.join("other-java"), b"").unwrap();
+        std::os::unix::fs::symlink(&home, dir.join("default-java")).unwrap();
+        std::os::unix::fs::symlink(home.join("bin/java"), dir.join("java")).unwrap();
+        let java = home.join("bin/java");
+        let other = dir.join("other-java");
+
+        let mut candidates = CandidateList::default();
+        candidates.add(dir.join("java"), "PATH");
+        candidates.add(dir.join("other-java"), "SDKMAN!");
+        candidates.add(home.join("bin/java"), "/usr/lib/jvm");
+        candidates.add(dir.join("default-java/bin/java"), "/usr/lib/jvm");
+        candidates.add(dir.join("java"), "JAVA_HOME");
+
+        let found: Vec<_> = candidates
+            .0
+            .iter()
+            .map(|c| (c.path.clone(), c.sources.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    java,
+                    vec!["PATH".into(), "/usr/lib/jvm".into(), "JAVA_HOME".into()]
+                ),
+                (other, vec!["SDKMAN!".into()]),
+            ]
+        );
+        assert_eq!(
+            candidates.0[0].aliases,
+            vec![dir.join("java"), dir.join("default-java/bin/java")]
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
 }
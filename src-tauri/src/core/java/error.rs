@@ -53,6 +53,37 @@ impl fmt::Display for JavaError {
 
 impl std::error::Error for JavaError {}
 
+/// Why a Java executable couldn't be probed for its version
+#[derive(Debug, Clone)]
+pub enum JavaProbeError {
+    /// The executable doesn't exist or couldn't be started
+    Spawn(String),
+    /// The executable didn't print its version within the probe timeout and was killed
+    ProbeTimedOut,
+    /// The output has no Java version in it, e.g. the file isn't a Java runtime
+    UnrecognizedOutput(String),
+}
+
+impl fmt::Display for JavaProbeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JavaProbeError::Spawn(msg) => write!(f, "Failed to run Java: {}", msg),
+            JavaProbeError::ProbeTimedOut => write!(f, "Java didn't report its version in time"),
+            JavaProbeError::UnrecognizedOutput(line) => {
+                write!(f, "Not a Java runtime, it printed: {}", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JavaProbeError {}
+
+impl From<JavaProbeError> for String {
+    fn from(err: JavaProbeError) -> Self {
+        err.to_string()
+    }
+}
+
 /// Convert JavaError to String for Tauri command results
 impl From<JavaError> for String {
     fn from(err: JavaError) -> Self {
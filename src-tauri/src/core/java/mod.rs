@@ -14,18 +14,11 @@ pub use error::JavaError;
 use ts_rs::TS;
 
 /// Remove the UNC prefix (\\?\) from Windows paths
-pub fn strip_unc_prefix(path: PathBuf) -> PathBuf {
-    #[cfg(target_os = "windows")]
-    {
-        let s = path.to_string_lossy().to_string();
-        if s.starts_with(r"\\?\\") {
-            return PathBuf::from(&s[4..]);
-        }
-    }
-    path
-}
+pub use crate::utils::path::strip_unc_prefix;
 
-use crate::core::downloader::{DownloadQueue, JavaDownloadProgress, PendingJavaDownload};
+use crate::core::downloader::{
+    Checksum, DownloadQueue, JavaDownloadProgress, PendingJavaDownload, shared_client,
+};
 use crate::utils::zip;
 use provider::JavaProvider;
 use providers::AdoptiumProvider;
@@ -42,6 +35,47 @@ pub struct JavaInstallation {
     pub vendor: String,
     pub source: String,
     pub is_64bit: bool,
+    /// Whether this process can run it, see [`JavaInfo::executable`]
+    pub executable: bool,
+}
+
+/// What running a Java executable told about it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "java/core.ts")]
+pub struct JavaInfo {
+    pub path: String,
+    /// Full version, e.g. "17.0.8" or "1.8.0_381"
+    pub version: String,
+    /// Feature release, e.g. 17 or 8
+    pub major: u32,
+    pub vendor: String,
+    /// "x64", "x86", "aarch64" or the `os.arch` of other platforms
+    pub arch: String,
+    pub is_64bit: bool,
+    /// Where the executable was found, e.g. "PATH" or "JAVA_HOME"
+    pub sources: Vec<String>,
+    /// Other paths leading to the same executable
+    pub aliases: Vec<String>,
+    /// Whether it comes from Flatpak or Snap, whose sandbox may keep it from the game directory
+    pub sandboxed: bool,
+    /// Whether this process can run it. `false` if probing it failed, or if it lacks the
+    /// permission to be run, which Javas read from their `release` file are only checked for.
+    pub executable: bool,
+}
+
+impl From<JavaInfo> for JavaInstallation {
+    fn from(info: JavaInfo) -> Self {
+        JavaInstallation {
+            path: info.path,
+            version: info.version,
+            arch: info.arch,
+            vendor: info.vendor,
+            source: "system".to_string(),
+            is_64bit: info.is_64bit,
+            executable: info.executable,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -161,7 +195,7 @@ pub async fn fetch_java_catalog(
     app_handle: &AppHandle,
     force_refresh: bool,
 ) -> Result<JavaCatalog, String> {
-    let provider = AdoptiumProvider::new();
+    let provider = AdoptiumProvider::new(shared_client(app_handle)?);
     provider
         .fetch_catalog(app_handle, force_refresh)
         .await
@@ -169,18 +203,19 @@ pub async fn fetch_java_catalog(
 }
 
 pub async fn fetch_java_release(
+    app_handle: &AppHandle,
     major_version: u32,
     image_type: ImageType,
 ) -> Result<JavaDownloadInfo, String> {
-    let provider = AdoptiumProvider::new();
+    let provider = AdoptiumProvider::new(shared_client(app_handle)?);
     provider
         .fetch_release(major_version, image_type)
         .await
         .map_err(|e| e.to_string())
 }
 
-pub async fn fetch_available_versions() -> Result<Vec<u32>, String> {
-    let provider = AdoptiumProvider::new();
+pub async fn fetch_available_versions(app_handle: &AppHandle) -> Result<Vec<u32>, String> {
+    let provider = AdoptiumProvider::new(shared_client(app_handle)?);
     provider
         .available_versions()
         .await
@@ -193,7 +228,7 @@ pub async fn download_and_install_java(
     image_type: ImageType,
     custom_path: Option<PathBuf>,
 ) -> Result<JavaInstallation, String> {
-    let provider = AdoptiumProvider::new();
+    let provider = AdoptiumProvider::new(shared_client(app_handle)?);
     let info = provider.fetch_release(major_version, image_type).await?;
     let file_name = info.file_name.clone();
 
@@ -230,7 +265,8 @@ pub async fn download_and_install_java(
         if let Some(expected_checksum) = &info.checksum {
             let data = std::fs::read(&archive_path)
                 .map_err(|e| format!("Failed to read downloaded file: {}", e))?;
-            !crate::core::downloader::verify_checksum(&data, Some(expected_checksum), None)
+            // Adoptium publishes sha256 checksums
+            !Checksum::Sha256(expected_checksum.clone()).verify(&data)
         } else {
             false
         }
@@ -342,39 +378,153 @@ fn find_top_level_dir(extract_dir: &PathBuf) -> Result<String, String> {
 }
 
 pub async fn detect_java_installations() -> Vec<JavaInstallation> {
-    let mut installations = Vec::new();
-    let candidates = detection::get_java_candidates();
+    // Already without duplicates and sorted newest first
+    detect_javas()
+        .await
+        .into_iter()
+        .map(JavaInstallation::from)
+        .collect()
+}
 
-    for candidate in candidates {
-        if let Some(java) = validation::check_java_installation(&candidate).await {
-            if !installations
-                .iter()
-                .any(|j: &JavaInstallation| j.path == java.path)
-            {
-                installations.push(java);
-            }
-        }
+/// Probe every Java found on the system, newest major version first. Javas that can't be
+/// started are listed as not executable. Other candidates that aren't a working Java
+/// runtime, or don't answer in time, are left out unless their location tells what they are.
+pub async fn detect_javas() -> Vec<JavaInfo> {
+    let candidates = java_candidates().await;
+    let (javas, _) = probe_candidates(candidates, &persistence::JavaProbeCache::default()).await;
+    javas
+}
+
+/// Like [`detect_javas`], but reusing the results of the last detection for executables that
+/// haven't changed since. `force` probes every Java again.
+pub async fn detect_javas_cached(app_handle: &AppHandle, force: bool) -> Vec<JavaInfo> {
+    let cache = if force {
+        persistence::JavaProbeCache::default()
+    } else {
+        persistence::load_probe_cache(app_handle)
+    };
+    let candidates = java_candidates().await;
+    let (javas, cache) = probe_candidates(candidates, &cache).await;
+    if let Err(e) = persistence::save_probe_cache(app_handle, &cache) {
+        log::warn!("Failed to save the Java probe cache: {}", e);
     }
+    javas
+}
 
-    installations.sort_by(|a, b| {
-        let v_a = validation::parse_java_version(&a.version);
-        let v_b = validation::parse_java_version(&b.version);
-        v_b.cmp(&v_a)
-    });
+/// Look for Java candidates on a blocking thread, as it walks directories and runs
+/// `java_home` on macOS
+async fn java_candidates() -> Vec<detection::JavaCandidate> {
+    tokio::task::spawn_blocking(detection::get_java_candidates)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to look for Java installations: {}", e);
+            Vec::new()
+        })
+}
 
-    installations
+/// Probe the candidates `cache` has no current result for. Returns the Javas newest first,
+/// and the cache of the candidates, which leaves out executables that are gone.
+async fn probe_candidates(
+    candidates: Vec<detection::JavaCandidate>,
+    cache: &persistence::JavaProbeCache,
+) -> (Vec<JavaInfo>, persistence::JavaProbeCache) {
+    let candidates = detection::resolve_shims(candidates).await;
+    let unprobed: Vec<PathBuf> = candidates
+        .iter()
+        .filter(|c| cache.get(&c.path).is_none())
+        .map(|c| c.path.clone())
+        .collect();
+    let mut probed = validation::probe_javas(&unprobed).await.into_iter();
+
+    let mut javas: Vec<JavaInfo> = Vec::new();
+    let mut next_cache = persistence::JavaProbeCache::default();
+    for candidate in candidates {
+        let result = match cache.get(&candidate.path) {
+            Some(info) => Ok(info.clone()),
+            None => probed
+                .next()
+                .expect("a probe result for every unprobed candidate"),
+        };
+        // What the location tells is the base, the probe result replaces what it found out
+        let info = match (result, candidate.hint) {
+            (Ok(info), hint) => {
+                next_cache.insert(info.clone());
+                match hint {
+                    Some(hint) if info.vendor == "Unknown" => JavaInfo {
+                        vendor: hint.vendor,
+                        ..info
+                    },
+                    _ => info,
+                }
+            }
+            // Not cached, so it's probed again next time
+            (Err(e), Some(hint)) => {
+                eprintln!(
+                    "[Java] Failed to probe {}, going by its location: {}",
+                    candidate.path.display(),
+                    e
+                );
+                JavaInfo {
+                    path: candidate.path.to_string_lossy().into_owned(),
+                    // Not known to work, so it isn't picked to launch the game
+                    executable: false,
+                    ..hint
+                }
+            }
+            // Listed so it can be shown why it isn't used
+            (Err(error::JavaProbeError::Spawn(e)), None) => {
+                eprintln!("[Java] Failed to run {}: {}", candidate.path.display(), e);
+                JavaInfo {
+                    path: candidate.path.to_string_lossy().into_owned(),
+                    version: "Unknown".to_string(),
+                    major: 0,
+                    vendor: "Unknown".to_string(),
+                    arch: "Unknown".to_string(),
+                    is_64bit: false,
+                    sources: Vec::new(),
+                    aliases: Vec::new(),
+                    sandboxed: false,
+                    executable: false,
+                }
+            }
+            (Err(e), None) => {
+                eprintln!("[Java] Skipping {}: {}", candidate.path.display(), e);
+                continue;
+            }
+        };
+        javas.push(JavaInfo {
+            sandboxed: candidate
+                .sources
+                .iter()
+                .any(|source| detection::SANDBOXED_SOURCES.contains(&source.as_str())),
+            // Cached results don't notice a changed permission
+            executable: info.executable && validation::is_executable(&candidate.path),
+            sources: candidate.sources,
+            aliases: candidate
+                .aliases
+                .iter()
+                .map(|alias| alias.to_string_lossy().into_owned())
+                .collect(),
+            ..info
+        });
+    }
+    javas.sort_by_key(|java| std::cmp::Reverse(java.major));
+    (javas, next_cache)
 }
 
 pub async fn get_recommended_java(required_major_version: Option<u64>) -> Option<JavaInstallation> {
-    let installations = detect_java_installations().await;
+    let mut installations = detect_java_installations()
+        .await
+        .into_iter()
+        .filter(|java| java.executable);
 
     if let Some(required) = required_major_version {
-        installations.into_iter().find(|java| {
+        installations.find(|java| {
             let major = validation::parse_java_version(&java.version);
             major >= required as u32
         })
     } else {
-        installations.into_iter().next()
+        installations.next()
     }
 }
 
@@ -387,7 +537,8 @@ pub async fn get_compatible_java(
 
     installations.into_iter().find(|java| {
         let major = validation::parse_java_version(&java.version);
-        validation::is_version_compatible(major, required_major_version, max_major_version)
+        java.executable
+            && validation::is_version_compatible(major, required_major_version, max_major_version)
     })
 }
 
@@ -399,7 +550,8 @@ pub async fn is_java_compatible(
     let java_path_buf = PathBuf::from(java_path);
     if let Some(java) = validation::check_java_installation(&java_path_buf).await {
         let major = validation::parse_java_version(&java.version);
-        validation::is_version_compatible(major, required_major_version, max_major_version)
+        java.executable
+            && validation::is_version_compatible(major, required_major_version, max_major_version)
     } else {
         false
     }
@@ -536,3 +688,149 @@ pub fn clear_pending_download(
     queue.remove(major_version, image_type);
     queue.save(app_handle)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write a shell script standing in for `java` that logs every run to `runs`
+    #[cfg(unix)]
+    fn fake_java(dir: &std::path::Path, name: &str, version: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let bin = dir.join(name).join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let path = bin.join("java");
+        let script = format!(
+            "#!/bin/sh\necho {} >> '{}'\necho 'openjdk version \"{}\"' >&2\n",
+            name,
+            dir.join("runs").display(),
+            version
+        );
+        std::fs::write(&path, script).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    fn candidate(path: &std::path::Path) -> detection::JavaCandidate {
+        detection::JavaCandidate {
+            path: path.to_path_buf(),
+            sources: vec!["PATH".to_string()],
+            aliases: Vec::new(),
+            hint: None,
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_probe_candidates_falls_back_to_hint() {
+        let dir = std::env::temp_dir().join(format!("dropout-javas-{}", uuid::Uuid::new_v4()));
+        let broken = fake_java(&dir, "zulu-17", "");
+        let unhinted = fake_java(&dir, "jdk", "");
+        // Print nothing a version can be read from
+        for java in [&broken, &unhinted] {
+            std::fs::write(java, "#!/bin/sh\necho 'Segmentation fault' >&2\n").unwrap();
+        }
+        let hint = validation::java_info_from_dir_name("", "zulu-17.0.8").unwrap();
+        let candidates = vec![
+            detection::JavaCandidate {
+                hint: Some(hint),
+                ..candidate(&broken)
+            },
+            candidate(&unhinted),
+        ];
+
+        let (javas, cache) =
+            probe_candidates(candidates, &persistence::JavaProbeCache::default()).await;
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(javas.len(), 1);
+        assert_eq!(javas[0].path, broken.to_string_lossy());
+        assert_eq!(javas[0].version, "17.0.8");
+        assert_eq!(javas[0].vendor, "Zulu (Azul Systems)");
+        assert_eq!(javas[0].sources, vec!["PATH".to_string()]);
+        assert!(!javas[0].executable);
+        assert!(cache.javas.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_probe_candidates_keeps_javas_that_cant_run() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = std::env::temp_dir().join(format!("dropout-javas-{}", uuid::Uuid::new_v4()));
+        let hinted = fake_java(&dir, "temurin-21", "21.0.1");
+        let released = fake_java(&dir, "jdk-17", "17.0.8");
+        let unhinted = fake_java(&dir, "jdk", "11.0.2");
+        for java in [&hinted, &released, &unhinted] {
+            std::fs::set_permissions(java, std::fs::Permissions::from_mode(0o644)).unwrap();
+        }
+        std::fs::write(
+            dir.join("jdk-17/release"),
+            "JAVA_VERSION=\"17.0.8\"\nIMPLEMENTOR=\"Eclipse Adoptium\"\nOS_ARCH=\"x86_64\"\n",
+        )
+        .unwrap();
+        let hint = validation::java_info_from_dir_name("", "temurin-21.0.1").unwrap();
+        let candidates = vec![
+            detection::JavaCandidate {
+                hint: Some(hint),
+                ..candidate(&hinted)
+            },
+            candidate(&released),
+            candidate(&unhinted),
+        ];
+
+        let (javas, _) =
+            probe_candidates(candidates, &persistence::JavaProbeCache::default()).await;
+        std::fs::remove_dir_all(&dir).ok();
+
+        let found: Vec<_> = javas
+            .iter()
+            .map(|java| (java.version.as_str(), java.vendor.as_str(), java.executable))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("21.0.1", "Temurin (Eclipse)", false),
+                ("17.0.8", "Eclipse Adoptium", false),
+                ("Unknown", "Unknown", false)
+            ]
+        );
+        assert_eq!(javas[2].path, unhinted.to_string_lossy());
+        let installation = JavaInstallation::from(javas[0].clone());
+        assert!(!installation.executable);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_probe_candidates_reuses_unchanged_javas() {
+        let dir = std::env::temp_dir().join(format!("dropout-javas-{}", uuid::Uuid::new_v4()));
+        let java17 = fake_java(&dir, "jdk-17", "17.0.8");
+        let java21 = fake_java(&dir, "jdk-21", "21.0.1");
+        let runs = || std::fs::read_to_string(dir.join("runs")).unwrap_or_default();
+
+        let candidates = vec![candidate(&java17), candidate(&java21)];
+        let (javas, cache) =
+            probe_candidates(candidates.clone(), &persistence::JavaProbeCache::default()).await;
+        let majors: Vec<u32> = javas.iter().map(|java| java.major).collect();
+        assert_eq!(majors, vec![21, 17]);
+        assert_eq!(javas[0].sources, vec!["PATH".to_string()]);
+        assert!(javas[0].executable && !javas[0].sandboxed);
+        assert_eq!(runs().lines().count(), 2);
+
+        // Unchanged executables aren't run again
+        let (javas, cache) = probe_candidates(candidates.clone(), &cache).await;
+        assert_eq!(javas.len(), 2);
+        assert_eq!(runs().lines().count(), 2);
+
+        // A changed executable is, and one that's gone is left out of the cache. The new
+        // version changes the size, as the rewrite may keep the modification time.
+        fake_java(&dir, "jdk-17", "17.0.10");
+        std::fs::remove_dir_all(dir.join("jdk-21")).unwrap();
+        let (javas, cache) = probe_candidates(vec![candidate(&java17)], &cache).await;
+        assert_eq!(javas[0].version, "17.0.10");
+        assert_eq!(runs().lines().last(), Some("jdk-17"));
+        assert_eq!(runs().lines().count(), 3);
+        assert_eq!(cache.javas.len(), 1);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,6 +1,8 @@
+use crate::core::java::JavaInfo;
 use crate::core::java::error::JavaError;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Manager};
 use ts_rs::TS;
 
@@ -113,3 +115,81 @@ pub fn update_last_detection_time(app_handle: &AppHandle) -> Result<(), JavaErro
         .as_secs();
     save_java_config(app_handle, &config)
 }
+
+/// A probed Java, with the size and modification time its executable had then
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedJava {
+    pub info: JavaInfo,
+    pub modified: u64,
+    pub size: u64,
+}
+
+/// Probe results of the last Java detection, by executable path
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JavaProbeCache {
+    pub javas: HashMap<String, CachedJava>,
+}
+
+impl JavaProbeCache {
+    /// The remembered probe result of `path`, if its executable hasn't changed since
+    pub fn get(&self, path: &Path) -> Option<&JavaInfo> {
+        let cached = self.javas.get(path.to_string_lossy().as_ref())?;
+        let (modified, size) = binary_fingerprint(path)?;
+        (cached.modified == modified && cached.size == size).then_some(&cached.info)
+    }
+
+    /// Remember the probe result of the executable at `info.path`
+    pub fn insert(&mut self, info: JavaInfo) {
+        if let Some((modified, size)) = binary_fingerprint(Path::new(&info.path)) {
+            self.javas.insert(
+                info.path.clone(),
+                CachedJava {
+                    info,
+                    modified,
+                    size,
+                },
+            );
+        }
+    }
+}
+
+/// Modification time in milliseconds since the epoch, and size of a file
+fn binary_fingerprint(path: &Path) -> Option<(u64, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_millis() as u64;
+    Some((modified, metadata.len()))
+}
+
+fn get_probe_cache_path(app_handle: &AppHandle) -> PathBuf {
+    app_handle
+        .path()
+        .app_data_dir()
+        .unwrap()
+        .join("java_probe_cache.json")
+}
+
+pub fn load_probe_cache(app_handle: &AppHandle) -> JavaProbeCache {
+    let cache_path = get_probe_cache_path(app_handle);
+    // A missing or unreadable cache only means every Java gets probed again
+    std::fs::read_to_string(&cache_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_probe_cache(app_handle: &AppHandle, cache: &JavaProbeCache) -> Result<(), JavaError> {
+    let cache_path = get_probe_cache_path(app_handle);
+    let content = serde_json::to_string_pretty(cache)?;
+
+    std::fs::create_dir_all(cache_path.parent().ok_or_else(|| {
+        JavaError::InvalidConfig("Java probe cache path has no parent directory".to_string())
+    })?)?;
+
+    std::fs::write(&cache_path, content)?;
+    Ok(())
+}
@@ -54,6 +54,8 @@ fn is_version_compatible(
     required_major_version: Option<u64>,
     max_major_version: Option<u32>,
 ) -> bool {
+    // A Java that can't be run can't launch the game whatever its version
     let major = validation::parse_java_version(&java.version);
-    validation::is_version_compatible(major, required_major_version, max_major_version)
+    java.executable
+        && validation::is_version_compatible(major, required_major_version, max_major_version)
 }
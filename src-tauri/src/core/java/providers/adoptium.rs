@@ -58,17 +58,19 @@ pub struct AvailableReleases {
     pub most_recent_feature_release: Option<u32>,
 }
 
-pub struct AdoptiumProvider;
+pub struct AdoptiumProvider {
+    client: reqwest::Client,
+}
 
 impl AdoptiumProvider {
-    pub fn new() -> Self {
-        Self
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
     }
 }
 
 impl Default for AdoptiumProvider {
     fn default() -> Self {
-        Self::new()
+        Self::new(reqwest::Client::new())
     }
 }
 
@@ -86,7 +88,7 @@ impl JavaProvider for AdoptiumProvider {
 
         let os = self.os_name();
         let arch = self.arch_name();
-        let client = reqwest::Client::new();
+        let client = &self.client;
 
         let releases_url = format!("{}/info/available_releases", ADOPTIUM_API_BASE);
         let available: AvailableReleases = client
@@ -229,8 +231,8 @@ impl JavaProvider for AdoptiumProvider {
             ADOPTIUM_API_BASE, major_version, os, arch, image_type
         );
 
-        let client = reqwest::Client::new();
-        let response = client
+        let response = self
+            .client
             .get(&url)
             .header("Accept", "application/json")
             .send()
@@ -268,7 +270,10 @@ impl JavaProvider for AdoptiumProvider {
     async fn available_versions(&self) -> Result<Vec<u32>, JavaError> {
         let url = format!("{}/info/available_releases", ADOPTIUM_API_BASE);
 
-        let response = reqwest::get(url)
+        let response = self
+            .client
+            .get(url)
+            .send()
             .await
             .map_err(|e| JavaError::NetworkError(format!("Network request failed: {}", e)))?;
 
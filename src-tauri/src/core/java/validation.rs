@@ -1,46 +1,300 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
 
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
+use tokio::sync::Semaphore;
 
-use crate::core::java::JavaInstallation;
+use crate::core::java::error::JavaProbeError;
+use crate::core::java::{JavaInfo, JavaInstallation};
+
+/// How long a Java executable may take to print its version before it's killed
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How many Java executables are probed at the same time
+const PROBE_CONCURRENCY: usize = 4;
 
 pub async fn check_java_installation(path: &PathBuf) -> Option<JavaInstallation> {
-    let path = path.clone();
-    tokio::task::spawn_blocking(move || check_java_installation_blocking(&path))
-        .await
-        .ok()?
+    probe_java(path).await.ok().map(JavaInstallation::from)
 }
 
-fn check_java_installation_blocking(path: &PathBuf) -> Option<JavaInstallation> {
-    let mut cmd = Command::new(path);
-    cmd.arg("-version");
+/// Probe several Java executables, a few at a time. The results are in the order of `paths`.
+pub async fn probe_javas(paths: &[PathBuf]) -> Vec<Result<JavaInfo, JavaProbeError>> {
+    probe_javas_with_timeout(paths, PROBE_TIMEOUT).await
+}
+
+async fn probe_javas_with_timeout(
+    paths: &[PathBuf],
+    timeout: Duration,
+) -> Vec<Result<JavaInfo, JavaProbeError>> {
+    let permits = Semaphore::new(PROBE_CONCURRENCY);
+    futures::future::join_all(paths.iter().map(|path| async {
+        let _permit = permits
+            .acquire()
+            .await
+            .expect("probe semaphore is never closed");
+        probe_java_with_timeout(path, timeout).await
+    }))
+    .await
+}
+
+/// Find out the version, vendor and architecture of a Java executable.
+///
+/// The `release` file of the Java home is read when it has all of them, which saves
+/// starting a JVM. Otherwise the executable is run with `-XshowSettings:properties`, which
+/// makes the JVM list its system properties before the usual `-version` banner. Runtimes
+/// that don't know the option are read from the banner alone.
+pub async fn probe_java(path: &Path) -> Result<JavaInfo, JavaProbeError> {
+    probe_java_with_timeout(path, PROBE_TIMEOUT).await
+}
+
+async fn probe_java_with_timeout(
+    path: &Path,
+    timeout: Duration,
+) -> Result<JavaInfo, JavaProbeError> {
+    if let Some(info) = read_release_file(path).await {
+        return Ok(info);
+    }
+
+    // Dropping the child on timeout kills it, so a hung JVM doesn't linger
+    let mut cmd = tokio::process::Command::new(path);
+    cmd.args(["-XshowSettings:properties", "-version"])
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
 
     // Hide console window
     #[cfg(target_os = "windows")]
     cmd.creation_flags(0x08000000);
 
-    let output = cmd.output().ok()?;
+    let output = tokio::time::timeout(timeout, cmd.output())
+        .await
+        .map_err(|_| JavaProbeError::ProbeTimedOut)?
+        .map_err(|e| JavaProbeError::Spawn(e.to_string()))?;
+
+    // The version is printed to stderr, some wrappers print it to stdout
+    let mut text = String::from_utf8_lossy(&output.stderr).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stdout));
+    parse_java_info(&path.to_string_lossy(), &text)
+}
+
+/// Read the `release` file of the Java home `path` belongs to, `<java_home>/bin/java`
+async fn read_release_file(path: &Path) -> Option<JavaInfo> {
+    // Candidates like /usr/bin/java are links into the Java home
+    let executable = tokio::fs::canonicalize(path).await.ok()?;
+    let java_home = executable.parent()?.parent()?;
+    let contents = tokio::fs::read_to_string(java_home.join("release"))
+        .await
+        .ok()?;
+    let info = parse_release_file(&path.to_string_lossy(), &contents)?;
+    Some(JavaInfo {
+        executable: is_executable(path),
+        ..info
+    })
+}
+
+/// Whether the file at `path` may be run by this process
+pub fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+/// `OS_ARCH` values of the release file that mean a 64-bit runtime
+const RELEASE_64BIT_ARCHES: &[&str] = &[
+    "x86_64",
+    "amd64",
+    "aarch64",
+    "ppc64",
+    "ppc64le",
+    "s390x",
+    "riscv64",
+    "loongarch64",
+];
+
+/// Read a JDK `release` file, whose lines look like `JAVA_VERSION="17.0.8"`. Files without
+/// the version, implementor or architecture give `None`, e.g. those of older OpenJDK 8 builds.
+pub fn parse_release_file(path: &str, contents: &str) -> Option<JavaInfo> {
+    let properties: HashMap<&str, &str> = contents
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim().trim_matches('"')))
+        .collect();
+    let version = properties.get("JAVA_VERSION")?.to_string();
+    let implementor = properties.get("IMPLEMENTOR")?;
+    let os_arch = properties.get("OS_ARCH")?;
+    let arch = normalize_architecture(os_arch);
+
+    let vendor_text = match properties.get("IMPLEMENTOR_VERSION") {
+        Some(implementor_version) => format!("{}\n{}", implementor_version, implementor),
+        None => implementor.to_string(),
+    };
+    let is_64bit = RELEASE_64BIT_ARCHES.contains(os_arch);
+
+    Some(JavaInfo {
+        path: path.to_string(),
+        major: parse_java_version(&version),
+        version,
+        vendor: extract_vendor(&vendor_text),
+        arch,
+        is_64bit,
+        sources: Vec::new(),
+        aliases: Vec::new(),
+        sandboxed: false,
+        executable: true,
+    })
+}
 
-    let version_output = String::from_utf8_lossy(&output.stderr);
+/// Read the output of `java -XshowSettings:properties -version`, or of `java -version`
+pub fn parse_java_info(path: &str, output: &str) -> Result<JavaInfo, JavaProbeError> {
+    // Properties are indented `key = value` lines, the banner lines aren't indented
+    let properties: HashMap<&str, &str> = output
+        .lines()
+        .filter(|line| line.starts_with(char::is_whitespace))
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect();
+    let banner = output
+        .lines()
+        .filter(|line| {
+            !line.starts_with(char::is_whitespace) && !line.starts_with("Property settings")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
 
-    let version = parse_version_string(&version_output)?;
-    let arch = extract_architecture(&version_output);
-    let vendor = extract_vendor(&version_output);
-    let is_64bit = version_output.to_lowercase().contains("64-bit") || arch == "aarch64";
+    let version = properties
+        .get("java.version")
+        .map(|version| version.to_string())
+        .or_else(|| parse_version_string(&banner))
+        .ok_or_else(|| {
+            JavaProbeError::UnrecognizedOutput(
+                banner.lines().next().unwrap_or_default().to_string(),
+            )
+        })?;
 
-    Some(JavaInstallation {
-        path: path.to_string_lossy().to_string(),
+    // The specification vendor is Oracle for every runtime, so only these name the build
+    let vendor_text = [
+        "java.vendor",
+        "java.vendor.version",
+        "java.vm.name",
+        "java.runtime.name",
+    ]
+    .iter()
+    .filter_map(|key| properties.get(key))
+    .chain(std::iter::once(&banner.as_str()))
+    .copied()
+    .collect::<Vec<_>>()
+    .join("\n");
+    let arch = match properties.get("os.arch") {
+        Some(os_arch) => normalize_architecture(os_arch),
+        None => extract_architecture(&banner),
+    };
+    let is_64bit = match properties.get("sun.arch.data.model") {
+        Some(model) => *model == "64",
+        None => banner.to_lowercase().contains("64-bit") || arch == "aarch64",
+    };
+
+    Ok(JavaInfo {
+        path: path.to_string(),
+        major: parse_java_version(&version),
         version,
+        vendor: extract_vendor(&vendor_text),
         arch,
-        vendor,
-        source: "system".to_string(),
         is_64bit,
+        sources: Vec::new(),
+        aliases: Vec::new(),
+        sandboxed: false,
+        executable: true,
+    })
+}
+
+/// Guess what a Java is from the name of its directory, e.g. "temurin-21.0.2+13.0.LTS" or
+/// "graalvm-community-21.0.2". The architecture is assumed to be the launcher's own.
+pub fn java_info_from_dir_name(path: &str, name: &str) -> Option<JavaInfo> {
+    let parts: Vec<&str> = name.split('-').collect();
+    let version_at = parts
+        .iter()
+        .position(|part| part.starts_with(|c: char| c.is_ascii_digit()))?;
+    if version_at == 0 {
+        return None;
+    }
+    let version = parts[version_at..].join("-");
+    let major = parse_java_version(&version);
+    if major == 0 {
+        return None;
+    }
+    let arch = normalize_architecture(std::env::consts::ARCH);
+
+    Some(JavaInfo {
+        path: path.to_string(),
+        version,
+        major,
+        vendor: extract_vendor(&parts[..version_at].join("-")),
+        is_64bit: arch.ends_with("64"),
+        arch,
+        sources: Vec::new(),
+        aliases: Vec::new(),
+        sandboxed: false,
+        executable: true,
     })
 }
 
+/// Guess what a Java is from the name SDKMAN! gives its directory, `<version>-<vendor id>`,
+/// e.g. "21.0.3-tem" or "22.3.r17-grl" for GraalVM 22.3 on Java 17
+pub fn java_info_from_sdkman_name(path: &str, name: &str) -> Option<JavaInfo> {
+    let (version, id) = name.rsplit_once('-')?;
+    // SDKMAN! ids name the vendor, their short forms are looked up under the full name
+    let vendor_key = match id {
+        "tem" => "temurin",
+        "amzn" => "corretto",
+        "graal" | "graalce" | "grl" => "graalvm",
+        "ms" => "microsoft",
+        "sapmchn" => "sapmachine",
+        "librca" | "nik" => "liberica",
+        "sem" => "semeru",
+        "open" => "openjdk",
+        other => other,
+    };
+    // GraalVM releases before 22.3 name the Java they're built on after an "r"
+    let major = match version.split_once(".r") {
+        Some((_, java)) => parse_java_version(java),
+        None => parse_java_version(version),
+    };
+    if major == 0 {
+        return None;
+    }
+    let arch = normalize_architecture(std::env::consts::ARCH);
+
+    Some(JavaInfo {
+        path: path.to_string(),
+        version: version.to_string(),
+        major,
+        vendor: extract_vendor(vendor_key),
+        is_64bit: arch.ends_with("64"),
+        arch,
+        sources: Vec::new(),
+        aliases: Vec::new(),
+        sandboxed: false,
+        executable: true,
+    })
+}
+
+/// Name an `os.arch` value the way the rest of the launcher does
+fn normalize_architecture(os_arch: &str) -> String {
+    match os_arch {
+        "amd64" | "x86_64" => "x64",
+        "x86" | "i386" | "i486" | "i586" | "i686" => "x86",
+        "aarch64" | "arm64" => "aarch64",
+        other => other,
+    }
+    .to_string()
+}
+
 pub fn parse_version_string(output: &str) -> Option<String> {
     for line in output.lines() {
         if line.contains("version") {
@@ -51,19 +305,32 @@ pub fn parse_version_string(output: &str) -> Option<String> {
             }
         }
     }
-    None
+    // `java --version` style banners leave out the quotes, e.g. "openjdk 21.0.1 2023-10-17"
+    let first = output.lines().next()?;
+    let mut words = first.split_whitespace();
+    match words.next()? {
+        "java" | "openjdk" => words
+            .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+            .map(str::to_string),
+        _ => None,
+    }
 }
 
 pub fn parse_java_version(version: &str) -> u32 {
+    // Early access builds are named like "21-ea", so only the leading digits count
+    let number = |part: &str| {
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse().ok()
+    };
     let parts: Vec<&str> = version.split('.').collect();
     if let Some(first) = parts.first() {
         // Handle both legacy (1.x) and modern (x) versioning
         if *first == "1" {
             // Legacy versioning
-            parts.get(1).and_then(|s| s.parse().ok()).unwrap_or(0)
+            parts.get(1).and_then(|s| number(s)).unwrap_or(0)
         } else {
             // Modern versioning
-            first.parse().unwrap_or(0)
+            number(first).unwrap_or(0)
         }
     } else {
         0
@@ -71,12 +338,13 @@ pub fn parse_java_version(version: &str) -> u32 {
 }
 
 pub fn extract_architecture(version_output: &str) -> String {
-    if version_output.contains("64-Bit") {
+    // ARM builds also call their VM "64-Bit", so they're checked first
+    if version_output.contains("aarch64") || version_output.contains("ARM64") {
+        "aarch64".to_string()
+    } else if version_output.contains("64-Bit") {
         "x64".to_string()
     } else if version_output.contains("32-Bit") {
         "x86".to_string()
-    } else if version_output.contains("aarch64") || version_output.contains("ARM64") {
-        "aarch64".to_string()
     } else {
         "x64".to_string()
     }
@@ -85,7 +353,8 @@ pub fn extract_architecture(version_output: &str) -> String {
 pub fn extract_vendor(version_output: &str) -> String {
     let lower = version_output.to_lowercase();
 
-    let vendor_name: HashMap<&str, &str> = [
+    // Checked in order: distributions first, as their banners also mention OpenJDK
+    let vendor_name: &[(&str, &str)] = &[
         // Eclipse/Adoptium
         ("temurin", "Temurin (Eclipse)"),
         ("adoptium", "Eclipse Adoptium"),
@@ -97,8 +366,6 @@ pub fn extract_vendor(version_output: &str) -> String {
         ("albba", "Dragonwell (Alibaba)"),
         // GraalVM
         ("graalvm", "GraalVM"),
-        // Oracle
-        ("oracle", "Java SE Development Kit (Oracle)"),
         // Tencent
         ("kona", "Kona (Tencent)"),
         // BellSoft
@@ -113,16 +380,19 @@ pub fn extract_vendor(version_output: &str) -> String {
         ("sem", "Semeru (IBM)"),
         // Azul
         ("zulu", "Zulu (Azul Systems)"),
+        ("azul", "Zulu (Azul Systems)"),
         // Trava
         ("trava", "Trava (Trava)"),
         // Huawei
         ("bisheng", "BiSheng (Huawei)"),
+        // JetBrains
+        ("jbr", "JetBrains Runtime"),
+        ("jetbrains", "JetBrains Runtime"),
+        // Oracle, after GraalVM which Oracle builds too
+        ("oracle", "Java SE Development Kit (Oracle)"),
         // Generic OpenJDK
         ("openjdk", "OpenJDK"),
-    ]
-    .iter()
-    .cloned()
-    .collect();
+    ];
 
     for (key, name) in vendor_name {
         if lower.contains(key) {
@@ -144,3 +414,345 @@ pub fn is_version_compatible(
     let meets_max = max_major_version.map(|m| major <= m).unwrap_or(true);
     meets_min && meets_max
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMURIN_17: &str = "Property settings:
+    file.encoding = UTF-8
+    java.home = /usr/lib/jvm/temurin-17-jdk-amd64
+    java.library.path = /usr/java/packages/lib
+        /usr/lib64
+    java.runtime.name = OpenJDK Runtime Environment
+    java.runtime.version = 17.0.8+7
+    java.specification.vendor = Oracle Corporation
+    java.vendor = Eclipse Adoptium
+    java.vendor.version = Temurin-17.0.8+7
+    java.version = 17.0.8
+    java.vm.name = OpenJDK 64-Bit Server VM
+    java.vm.specification.vendor = Oracle Corporation
+    os.arch = amd64
+    sun.arch.data.model = 64
+
+openjdk version \"17.0.8\" 2023-07-18
+OpenJDK Runtime Environment Temurin-17.0.8+7 (build 17.0.8+7)
+OpenJDK 64-Bit Server VM Temurin-17.0.8+7 (build 17.0.8+7, mixed mode, sharing)
+";
+
+    const OPENJDK_21_ARM: &str = "Property settings:
+    java.runtime.name = OpenJDK Runtime Environment
+    java.specification.vendor = Oracle Corporation
+    java.vendor = Ubuntu
+    java.version = 21.0.2
+    java.vm.name = OpenJDK 64-Bit Server VM
+    os.arch = aarch64
+    sun.arch.data.model = 64
+
+openjdk version \"21.0.2\" 2024-01-16
+OpenJDK Runtime Environment (build 21.0.2+13-Ubuntu-122.04.1)
+OpenJDK 64-Bit Server VM (build 21.0.2+13-Ubuntu-122.04.1, mixed mode, sharing)
+";
+
+    const ORACLE_8_32BIT: &str = "Property settings:
+    java.runtime.name = Java(TM) SE Runtime Environment
+    java.specification.vendor = Oracle Corporation
+    java.vendor = Oracle Corporation
+    java.version = 1.8.0_381
+    java.vm.name = Java HotSpot(TM) Client VM
+    os.arch = x86
+    sun.arch.data.model = 32
+
+java version \"1.8.0_381\"
+Java(TM) SE Runtime Environment (build 1.8.0_381-b09)
+Java HotSpot(TM) Client VM (build 25.381-b09, mixed mode, sharing)
+";
+
+    const ZULU_11_BANNER: &str = "openjdk version \"11.0.20\" 2023-07-18 LTS
+OpenJDK Runtime Environment Zulu11.66+15-CA (build 11.0.20+8-LTS)
+OpenJDK 64-Bit Server VM Zulu11.66+15-CA (build 11.0.20+8-LTS, mixed mode)
+";
+
+    const GRAALVM_21_BANNER: &str = "java 21.0.1 2023-10-17
+Java(TM) SE Runtime Environment Oracle GraalVM 21.0.1+12.1 (build 21.0.1+12-jvmci-23.1-b19)
+Java HotSpot(TM) 64-Bit Server VM Oracle GraalVM 21.0.1+12.1 (build 21.0.1+12-jvmci-23.1-b19, mixed mode, sharing)
+";
+
+    const TEMURIN_21_RELEASE: &str = r#"IMPLEMENTOR="Eclipse Adoptium"
+IMPLEMENTOR_VERSION="Temurin-21.0.1+12"
+JAVA_RUNTIME_VERSION="21.0.1+12-LTS"
+JAVA_VERSION="21.0.1"
+JAVA_VERSION_DATE="2023-10-17"
+LIBC="gnu"
+MODULES="java.base java.compiler java.datatransfer"
+OS_ARCH="x86_64"
+OS_NAME="Linux"
+SOURCE=".:git:5d6d2c1ae2b3"
+"#;
+
+    const CORRETTO_17_RELEASE: &str = r#"IMPLEMENTOR="Amazon.com Inc."
+IMPLEMENTOR_VERSION="Corretto-17.0.9.8.1"
+JAVA_VERSION="17.0.9"
+JAVA_VERSION_DATE="2023-10-17"
+MODULES="java.base java.compiler"
+OS_ARCH="aarch64"
+OS_NAME="Darwin"
+SOURCE=".:git:a1b2c3d4e5f6"
+"#;
+
+    const ZULU_8_RELEASE: &str = r#"JAVA_VERSION="1.8.0_392"
+OS_NAME="Windows"
+OS_VERSION="5.2"
+OS_ARCH="i586"
+SOURCE=" .:git:9e2c3f4d"
+IMPLEMENTOR="Azul Systems, Inc."
+IMPLEMENTOR_VERSION="Zulu8.74.0.17-CA-win32"
+"#;
+
+    const OPENJDK_8_RELEASE: &str = r#"JAVA_VERSION="1.8.0_392"
+OS_NAME="Linux"
+OS_ARCH="amd64"
+"#;
+
+    fn release(contents: &str) -> Option<(String, u32, String, String, bool)> {
+        let info = parse_release_file("java", contents)?;
+        Some((
+            info.version,
+            info.major,
+            info.vendor,
+            info.arch,
+            info.is_64bit,
+        ))
+    }
+
+    #[test]
+    fn test_parse_release_file() {
+        assert_eq!(
+            release(TEMURIN_21_RELEASE),
+            Some((
+                "21.0.1".into(),
+                21,
+                "Temurin (Eclipse)".into(),
+                "x64".into(),
+                true
+            ))
+        );
+        assert_eq!(
+            release(CORRETTO_17_RELEASE),
+            Some((
+                "17.0.9".into(),
+                17,
+                "Corretto (Amazon)".into(),
+                "aarch64".into(),
+                true
+            ))
+        );
+        assert_eq!(
+            release(ZULU_8_RELEASE),
+            Some((
+                "1.8.0_392".into(),
+                8,
+                "Zulu (Azul Systems)".into(),
+                "x86".into(),
+                false
+            ))
+        );
+        let s390x = TEMURIN_21_RELEASE.replace("x86_64", "s390x");
+        assert_eq!(
+            release(&s390x).map(|r| (r.3, r.4)),
+            Some(("s390x".into(), true))
+        );
+        let arm32 = TEMURIN_21_RELEASE.replace("x86_64", "arm");
+        assert_eq!(
+            release(&arm32).map(|r| (r.3, r.4)),
+            Some(("arm".into(), false))
+        );
+        // Without an implementor java has to be run to tell the vendor
+        assert_eq!(release(OPENJDK_8_RELEASE), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_probe_java_prefers_release_file() {
+        let home = std::env::temp_dir().join(format!("dropout-release-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(home.join("bin")).unwrap();
+        // Running this java would fail, so the result has to come from the release file
+        let java = fake_java(&home.join("bin"), "java", "exit 1");
+        std::fs::write(home.join("release"), CORRETTO_17_RELEASE).unwrap();
+
+        let info = probe_java(&java).await.unwrap();
+        assert_eq!(info.path, java.to_string_lossy());
+        assert_eq!(
+            (info.major, info.vendor.as_str()),
+            (17, "Corretto (Amazon)")
+        );
+
+        // An incomplete release file falls back to running java
+        std::fs::write(home.join("release"), OPENJDK_8_RELEASE).unwrap();
+        assert!(probe_java(&java).await.is_err());
+        std::fs::remove_dir_all(&home).ok();
+    }
+
+    fn probe(output: &str) -> (String, u32, String, String, bool) {
+        let info = parse_java_info("java", output).unwrap();
+        (
+            info.version,
+            info.major,
+            info.vendor,
+            info.arch,
+            info.is_64bit,
+        )
+    }
+
+    #[test]
+    fn test_parse_java_info_from_properties() {
+        assert_eq!(
+            probe(TEMURIN_17),
+            (
+                "17.0.8".into(),
+                17,
+                "Temurin (Eclipse)".into(),
+                "x64".into(),
+                true
+            )
+        );
+        assert_eq!(
+            probe(OPENJDK_21_ARM),
+            (
+                "21.0.2".into(),
+                21,
+                "OpenJDK".into(),
+                "aarch64".into(),
+                true
+            )
+        );
+        assert_eq!(
+            probe(ORACLE_8_32BIT),
+            (
+                "1.8.0_381".into(),
+                8,
+                "Java SE Development Kit (Oracle)".into(),
+                "x86".into(),
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_java_info_from_banner() {
+        assert_eq!(
+            probe(ZULU_11_BANNER),
+            (
+                "11.0.20".into(),
+                11,
+                "Zulu (Azul Systems)".into(),
+                "x64".into(),
+                true
+            )
+        );
+        assert_eq!(
+            probe(GRAALVM_21_BANNER),
+            ("21.0.1".into(), 21, "GraalVM".into(), "x64".into(), true)
+        );
+    }
+
+    #[test]
+    fn test_parse_java_info_rejects_other_output() {
+        let result = parse_java_info("python", "Python 3.12.1\n");
+        assert!(matches!(result, Err(JavaProbeError::UnrecognizedOutput(_))));
+    }
+
+    /// Write an executable shell script standing in for `java`
+    #[cfg(unix)]
+    fn fake_java(dir: &Path, name: &str, script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = dir.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_probe_javas_times_out_hung_candidates() {
+        let dir = std::env::temp_dir().join(format!("dropout-probe-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let banner = "echo 'openjdk version \"17.0.8\" 2023-07-18' >&2; sleep 0.5";
+        let mut paths: Vec<PathBuf> = (0..4)
+            .map(|i| fake_java(&dir, &format!("java{}", i), banner))
+            .collect();
+        paths.insert(1, fake_java(&dir, "hung", "sleep 30"));
+
+        let started = std::time::Instant::now();
+        let results = probe_javas_with_timeout(&paths, Duration::from_secs(1)).await;
+        let elapsed = started.elapsed();
+
+        assert!(matches!(results[1], Err(JavaProbeError::ProbeTimedOut)));
+        for (i, result) in results.iter().enumerate().filter(|(i, _)| *i != 1) {
+            assert_eq!(result.as_ref().unwrap().major, 17, "candidate {}", i);
+        }
+        // Probed one at a time, the working candidates alone would take 2 s
+        assert!(elapsed < Duration::from_millis(1900), "took {:?}", elapsed);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_probe_java_reports_missing_executable() {
+        let result = probe_java(Path::new("/nonexistent/dropout/bin/java")).await;
+        assert!(matches!(result, Err(JavaProbeError::Spawn(_))));
+    }
+
+    #[test]
+    fn test_java_info_from_dir_name() {
+        let info = java_info_from_dir_name("java", "temurin-21.0.2+13.0.LTS").unwrap();
+        assert_eq!(
+            (info.version.as_str(), info.major, info.vendor.as_str()),
+            ("21.0.2+13.0.LTS", 21, "Temurin (Eclipse)")
+        );
+        let info = java_info_from_dir_name("java", "graalvm-community-21.0.2").unwrap();
+        assert_eq!((info.major, info.vendor.as_str()), (21, "GraalVM"));
+        let info = java_info_from_dir_name("java", "adoptopenjdk-8.0.392+8").unwrap();
+        assert_eq!((info.major, info.vendor.as_str()), (8, "OpenJDK"));
+        let info = java_info_from_dir_name("java", "jbr_jcef-17.0.9").unwrap();
+        assert_eq!(
+            (info.major, info.vendor.as_str()),
+            (17, "JetBrains Runtime")
+        );
+        assert_eq!(java_info_from_dir_name("java", "latest"), None);
+        assert_eq!(java_info_from_dir_name("java", "17"), None);
+    }
+
+    #[test]
+    fn test_java_info_from_sdkman_name() {
+        let hint = |name| {
+            java_info_from_sdkman_name("java", name)
+                .map(|info| (info.version, info.major, info.vendor))
+        };
+        assert_eq!(
+            hint("21.0.3-tem"),
+            Some(("21.0.3".into(), 21, "Temurin (Eclipse)".into()))
+        );
+        assert_eq!(
+            hint("8.0.402-amzn"),
+            Some(("8.0.402".into(), 8, "Corretto (Amazon)".into()))
+        );
+        assert_eq!(
+            hint("22.3.r17-grl"),
+            Some(("22.3.r17".into(), 17, "GraalVM".into()))
+        );
+        assert_eq!(
+            hint("17.0.9-zulu"),
+            Some(("17.0.9".into(), 17, "Zulu (Azul Systems)".into()))
+        );
+        assert_eq!(hint("21-open"), Some(("21".into(), 21, "OpenJDK".into())));
+        assert_eq!(hint("current"), None);
+    }
+
+    #[test]
+    fn test_parse_java_version() {
+        assert_eq!(parse_java_version("1.8.0_381"), 8);
+        assert_eq!(parse_java_version("17.0.8"), 17);
+        assert_eq!(parse_java_version("21-ea"), 21);
+        assert_eq!(parse_java_version("unknown"), 0);
+    }
+}
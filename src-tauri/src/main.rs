@@ -272,8 +272,25 @@ async fn start_game(
     download_tasks.push(core::downloader::DownloadTask {
         url: client_jar.url.clone(),
         path: client_path.clone(),
-        sha1: client_jar.sha1.clone(),
-        sha256: None,
+        checksum: client_jar
+            .sha1
+            .clone()
+            .map(core::downloader::Checksum::Sha1),
+        size: client_jar.size,
+        fallback_urls: Vec::new(),
+        id: None,
+        headers: None,
+        // The jar and libraries are needed to launch, so they go before the assets
+        priority: 1,
+        extract_to: None,
+        archive_kind: None,
+        cacheable: false,
+        executable: false,
+        fetch_sidecar_hash: false,
+        group: Some("client".to_string()),
+        existing_file_policy: None,
+        overwrite: None,
+        auth: None,
     });
 
     // --- Libraries ---
@@ -302,8 +319,21 @@ async fn start_game(
                     download_tasks.push(core::downloader::DownloadTask {
                         url: artifact.url.clone(),
                         path: lib_path,
-                        sha1: artifact.sha1.clone(),
-                        sha256: None,
+                        checksum: artifact.sha1.clone().map(core::downloader::Checksum::Sha1),
+                        size: artifact.size,
+                        fallback_urls: Vec::new(),
+                        id: None,
+                        headers: None,
+                        priority: 1,
+                        extract_to: None,
+                        archive_kind: None,
+                        cacheable: false,
+                        executable: false,
+                        fetch_sidecar_hash: false,
+                        group: Some("libraries".to_string()),
+                        existing_file_policy: None,
+                        overwrite: None,
+                        auth: None,
                     });
                 }
 
@@ -351,8 +381,21 @@ async fn start_game(
                         download_tasks.push(core::downloader::DownloadTask {
                             url: native_artifact.url,
                             path: native_path.clone(),
-                            sha1: native_artifact.sha1,
-                            sha256: None,
+                            checksum: native_artifact.sha1.map(core::downloader::Checksum::Sha1),
+                            size: native_artifact.size,
+                            fallback_urls: Vec::new(),
+                            id: None,
+                            headers: None,
+                            priority: 1,
+                            extract_to: None,
+                            archive_kind: None,
+                            cacheable: false,
+                            executable: false,
+                            fetch_sidecar_hash: false,
+                            group: Some("natives".to_string()),
+                            existing_file_policy: None,
+                            overwrite: None,
+                            auth: None,
                         });
 
                         native_libs_paths.push(native_path);
@@ -369,8 +412,21 @@ async fn start_game(
                         download_tasks.push(core::downloader::DownloadTask {
                             url,
                             path: lib_path,
-                            sha1: None, // Maven libraries often don't have SHA1 in the JSON
-                            sha256: None,
+                            checksum: None, // Maven libraries often don't have SHA1 in the JSON, but publish a .sha1 next to the jar
+                            size: None,
+                            fallback_urls: Vec::new(),
+                            id: None,
+                            headers: None,
+                            priority: 1,
+                            extract_to: None,
+                            archive_kind: None,
+                            cacheable: false,
+                            executable: false,
+                            fetch_sidecar_hash: true,
+                            group: Some("libraries".to_string()),
+                            existing_file_policy: None,
+                            overwrite: None,
+                            auth: None,
                         });
                     }
                 }
@@ -429,7 +485,6 @@ async fn start_game(
     #[derive(serde::Deserialize, Debug)]
     struct AssetObject {
         hash: String,
-        #[allow(dead_code)]
         size: u64,
     }
 
@@ -455,8 +510,21 @@ async fn start_game(
         download_tasks.push(core::downloader::DownloadTask {
             url,
             path,
-            sha1: Some(hash),
-            sha256: None,
+            checksum: Some(core::downloader::Checksum::Sha1(hash)),
+            size: Some(object.size),
+            fallback_urls: Vec::new(),
+            id: None,
+            headers: None,
+            priority: 0,
+            extract_to: None,
+            archive_kind: None,
+            cacheable: false,
+            executable: false,
+            fetch_sidecar_hash: false,
+            group: Some("assets".to_string()),
+            existing_file_policy: None,
+            overwrite: None,
+            auth: None,
         });
     }
 
@@ -477,12 +545,16 @@ async fn start_game(
         )
     );
     core::downloader::download_files(
-        window.clone(),
+        std::sync::Arc::new(core::downloader::WindowSink(window.clone())),
         download_tasks,
-        config.download_threads as usize,
+        core::downloader::DownloadOptions {
+            state_dir: core::downloader::batch_state_dir(window.app_handle()),
+            client: Some(core::downloader::shared_client(window.app_handle())?),
+            ..core::downloader::DownloadOptions::from_config(&config)
+        },
     )
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?
+    .into_result()?;
     emit_log!(window, "All downloads completed successfully".to_string());
 
     // 5. Extract Natives
@@ -1098,8 +1170,25 @@ async fn install_version(
     download_tasks.push(core::downloader::DownloadTask {
         url: client_jar.url.clone(),
         path: client_path.clone(),
-        sha1: client_jar.sha1.clone(),
-        sha256: None,
+        checksum: client_jar
+            .sha1
+            .clone()
+            .map(core::downloader::Checksum::Sha1),
+        size: client_jar.size,
+        fallback_urls: Vec::new(),
+        id: None,
+        headers: None,
+        // The jar and libraries are needed to launch, so they go before the assets
+        priority: 1,
+        extract_to: None,
+        archive_kind: None,
+        cacheable: false,
+        executable: false,
+        fetch_sidecar_hash: false,
+        group: Some("client".to_string()),
+        existing_file_policy: None,
+        overwrite: None,
+        auth: None,
     });
 
     // --- Libraries ---
@@ -1130,8 +1219,21 @@ async fn install_version(
                     download_tasks.push(core::downloader::DownloadTask {
                         url: artifact.url.clone(),
                         path: lib_path,
-                        sha1: artifact.sha1.clone(),
-                        sha256: None,
+                        checksum: artifact.sha1.clone().map(core::downloader::Checksum::Sha1),
+                        size: artifact.size,
+                        fallback_urls: Vec::new(),
+                        id: None,
+                        headers: None,
+                        priority: 1,
+                        extract_to: None,
+                        archive_kind: None,
+                        cacheable: false,
+                        executable: false,
+                        fetch_sidecar_hash: false,
+                        group: Some("libraries".to_string()),
+                        existing_file_policy: None,
+                        overwrite: None,
+                        auth: None,
                     });
                 }
 
@@ -1178,8 +1280,21 @@ async fn install_version(
                         download_tasks.push(core::downloader::DownloadTask {
                             url: native_artifact.url,
                             path: native_path.clone(),
-                            sha1: native_artifact.sha1,
-                            sha256: None,
+                            checksum: native_artifact.sha1.map(core::downloader::Checksum::Sha1),
+                            size: native_artifact.size,
+                            fallback_urls: Vec::new(),
+                            id: None,
+                            headers: None,
+                            priority: 1,
+                            extract_to: None,
+                            archive_kind: None,
+                            cacheable: false,
+                            executable: false,
+                            fetch_sidecar_hash: false,
+                            group: Some("natives".to_string()),
+                            existing_file_policy: None,
+                            overwrite: None,
+                            auth: None,
                         });
                     }
                 }
@@ -1193,8 +1308,21 @@ async fn install_version(
                         download_tasks.push(core::downloader::DownloadTask {
                             url,
                             path: lib_path,
-                            sha1: None,
-                            sha256: None,
+                            checksum: None,
+                            size: None,
+                            fallback_urls: Vec::new(),
+                            id: None,
+                            headers: None,
+                            priority: 1,
+                            extract_to: None,
+                            archive_kind: None,
+                            cacheable: false,
+                            executable: false,
+                            fetch_sidecar_hash: true,
+                            group: Some("libraries".to_string()),
+                            existing_file_policy: None,
+                            overwrite: None,
+                            auth: None,
                         });
                     }
                 }
@@ -1249,6 +1377,7 @@ async fn install_version(
     #[derive(serde::Deserialize)]
     struct AssetObject {
         hash: String,
+        size: u64,
     }
 
     #[derive(serde::Deserialize)]
@@ -1276,8 +1405,21 @@ async fn install_version(
         download_tasks.push(core::downloader::DownloadTask {
             url,
             path,
-            sha1: Some(hash),
-            sha256: None,
+            checksum: Some(core::downloader::Checksum::Sha1(hash)),
+            size: Some(object.size),
+            fallback_urls: Vec::new(),
+            id: None,
+            headers: None,
+            priority: 0,
+            extract_to: None,
+            archive_kind: None,
+            cacheable: false,
+            executable: false,
+            fetch_sidecar_hash: false,
+            group: Some("assets".to_string()),
+            existing_file_policy: None,
+            overwrite: None,
+            auth: None,
         });
     }
 
@@ -1298,12 +1440,16 @@ async fn install_version(
         )
     );
     core::downloader::download_files(
-        window.clone(),
+        std::sync::Arc::new(core::downloader::WindowSink(window.clone())),
         download_tasks,
-        config.download_threads as usize,
+        core::downloader::DownloadOptions {
+            state_dir: core::downloader::batch_state_dir(window.app_handle()),
+            client: Some(core::downloader::shared_client(window.app_handle())?),
+            ..core::downloader::DownloadOptions::from_config(&config)
+        },
     )
-    .await
-    .map_err(|e| e.to_string())?;
+    .await?
+    .into_result()?;
 
     emit_log!(
         window,
@@ -1387,8 +1533,14 @@ async fn get_settings(
 #[dropout_macros::api]
 async fn save_settings(
     state: State<'_, core::config::ConfigState>,
+    http_client: State<'_, core::downloader::HttpClient>,
+    download_manager: State<'_, core::downloader::DownloadManager>,
     config: core::config::LauncherConfig,
 ) -> Result<(), String> {
+    config.network.validate()?;
+    // An invalid proxy is reported by the requests sent with the client
+    let _ = http_client.rebuild(&core::downloader::DownloadOptions::from_config(&config));
+    download_manager.set_speed_limit(config.download_speed_limit);
     *state.config.lock().unwrap() = config;
     state.save()?;
     Ok(())
@@ -1559,6 +1711,23 @@ async fn detect_java(
     Ok(core::java::detect_all_java_installations(&app_handle).await)
 }
 
+/// Probe the Java installations found on the system for their version, vendor and architecture
+#[tauri::command]
+#[dropout_macros::api]
+async fn detect_javas(app_handle: tauri::AppHandle) -> Result<Vec<core::java::JavaInfo>, String> {
+    Ok(core::java::detect_javas_cached(&app_handle, false).await)
+}
+
+/// Detect Java installations again, probing every one of them when `force` is set
+#[tauri::command]
+#[dropout_macros::api]
+async fn refresh_javas(
+    app_handle: tauri::AppHandle,
+    force: bool,
+) -> Result<Vec<core::java::JavaInfo>, String> {
+    Ok(core::java::detect_javas_cached(&app_handle, force).await)
+}
+
 /// Get recommended Java for a specific Minecraft version
 #[tauri::command]
 #[dropout_macros::api]
@@ -1572,6 +1741,7 @@ async fn get_recommended_java(
 #[tauri::command]
 #[dropout_macros::api]
 async fn fetch_adoptium_java(
+    app_handle: tauri::AppHandle,
     major_version: u32,
     image_type: String,
 ) -> Result<core::java::JavaDownloadInfo, String> {
@@ -1579,7 +1749,7 @@ async fn fetch_adoptium_java(
         "jdk" => core::java::ImageType::Jdk,
         _ => core::java::ImageType::Jre,
     };
-    core::java::fetch_java_release(major_version, img_type)
+    core::java::fetch_java_release(&app_handle, major_version, img_type)
         .await
         .map_err(|e| e.to_string())
 }
@@ -1606,8 +1776,8 @@ async fn download_adoptium_java(
 /// Get available Adoptium Java versions
 #[tauri::command]
 #[dropout_macros::api]
-async fn fetch_available_java_versions() -> Result<Vec<u32>, String> {
-    core::java::fetch_available_versions()
+async fn fetch_available_java_versions(app_handle: tauri::AppHandle) -> Result<Vec<u32>, String> {
+    core::java::fetch_available_versions(&app_handle)
         .await
         .map_err(|e| e.to_string())
 }
@@ -1660,6 +1830,264 @@ async fn resume_java_downloads(
     core::java::resume_pending_downloads(&app_handle).await
 }
 
+/// Pause a running download batch
+#[tauri::command]
+#[dropout_macros::api]
+async fn pause_download(
+    download_manager: State<'_, core::downloader::DownloadManager>,
+    batch_id: String,
+) -> Result<(), String> {
+    download_manager.pause(&batch_id)
+}
+
+/// Cancel one task of a running download batch without stopping the others
+#[tauri::command]
+#[dropout_macros::api]
+async fn cancel_task(
+    download_manager: State<'_, core::downloader::DownloadManager>,
+    batch_id: String,
+    task_id: String,
+) -> Result<core::downloader::CancelResult, String> {
+    download_manager.cancel_task(&batch_id, &task_id)
+}
+
+/// Resume a paused download batch
+#[tauri::command]
+#[dropout_macros::api]
+async fn resume_download(
+    download_manager: State<'_, core::downloader::DownloadManager>,
+    batch_id: String,
+) -> Result<(), String> {
+    download_manager.resume(&batch_id)
+}
+
+/// Add tasks to the download queue and return their ids. While the queue is being drained
+/// they start right away.
+#[tauri::command]
+#[dropout_macros::api]
+async fn enqueue_downloads(
+    task_queue: State<'_, std::sync::Arc<core::downloader::TaskQueue>>,
+    tasks: Vec<core::downloader::DownloadTask>,
+) -> Result<Vec<String>, String> {
+    Ok(task_queue.enqueue(tasks)?)
+}
+
+/// Batches that were still downloading when the app was closed
+#[tauri::command]
+#[dropout_macros::api]
+async fn get_pending_batches(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<core::downloader::PendingBatch>, String> {
+    Ok(core::downloader::batch_state_dir(&app_handle)
+        .map(|dir| core::downloader::pending_batches(&dir))
+        .unwrap_or_default())
+}
+
+/// Evict the least recently used files of the download cache until it holds at most
+/// `max_size` bytes
+#[tauri::command]
+#[dropout_macros::api]
+async fn prune_download_cache(
+    config_state: State<'_, core::config::ConfigState>,
+    max_size: u64,
+) -> Result<core::downloader::CachePruneReport, String> {
+    let dir = config_state
+        .config
+        .lock()
+        .unwrap()
+        .download_cache_dir
+        .clone();
+    let Some(dir) = dir else {
+        return Ok(core::downloader::CachePruneReport::default());
+    };
+    tokio::task::spawn_blocking(move || {
+        core::downloader::prune_cache(std::path::Path::new(&dir), max_size)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Continue a batch that was still downloading when the app was closed
+#[tauri::command]
+#[dropout_macros::api]
+async fn resume_batch(
+    window: Window,
+    config_state: State<'_, core::config::ConfigState>,
+    batch_id: String,
+) -> Result<core::downloader::DownloadBatchResult, core::downloader::DownloadError> {
+    let config = config_state.config.lock().unwrap().clone();
+    let dir = core::downloader::batch_state_dir(window.app_handle()).ok_or_else(|| {
+        core::downloader::DownloadError::other("Failed to resolve the app data directory")
+    })?;
+    let client = core::downloader::shared_client(window.app_handle())
+        .map_err(core::downloader::DownloadError::other)?;
+    let options = core::downloader::DownloadOptions {
+        state_dir: Some(dir.clone()),
+        client: Some(client),
+        ..core::downloader::DownloadOptions::from_config(&config)
+    };
+    let sink = std::sync::Arc::new(core::downloader::WindowSink(window));
+    core::downloader::resume_batch(sink, &dir, &batch_id, options).await
+}
+
+/// Download the tasks that failed in a batch again. Returns `None` when they joined the
+/// batch that's already draining the download queue.
+#[tauri::command]
+#[dropout_macros::api]
+async fn retry_failed(
+    window: Window,
+    config_state: State<'_, core::config::ConfigState>,
+    download_manager: State<'_, core::downloader::DownloadManager>,
+    task_queue: State<'_, std::sync::Arc<core::downloader::TaskQueue>>,
+    batch_id: String,
+) -> Result<Option<core::downloader::DownloadBatchResult>, core::downloader::DownloadError> {
+    let config = config_state.config.lock().unwrap().clone();
+    let client = core::downloader::shared_client(window.app_handle())
+        .map_err(core::downloader::DownloadError::other)?;
+    let options = core::downloader::DownloadOptions {
+        state_dir: core::downloader::batch_state_dir(window.app_handle()),
+        client: Some(client),
+        ..core::downloader::DownloadOptions::from_config(&config)
+    };
+    core::downloader::retry_failed(
+        std::sync::Arc::new(core::downloader::WindowSink(window)),
+        &download_manager,
+        task_queue.inner().clone(),
+        &batch_id,
+        options,
+    )
+    .await
+}
+
+/// Check which files of `tasks` are valid, missing or corrupt without downloading anything
+#[tauri::command]
+#[dropout_macros::api]
+async fn verify_files(
+    window: Window,
+    tasks: Vec<core::downloader::DownloadTask>,
+) -> Result<core::downloader::VerifyReport, String> {
+    let sink = core::downloader::WindowSink(window);
+    Ok(core::downloader::verify_files(&sink, &tasks).await)
+}
+
+/// Check which of `tasks` need to be downloaded and how many bytes that is, without
+/// downloading anything
+#[tauri::command]
+#[dropout_macros::api]
+async fn plan_download(
+    tasks: Vec<core::downloader::DownloadTask>,
+) -> Result<core::downloader::DownloadPlan, String> {
+    Ok(core::downloader::plan_download(&tasks).await)
+}
+
+/// Remove a task from the download queue, stopping it if it's already downloading
+#[tauri::command]
+#[dropout_macros::api]
+async fn remove_download(
+    task_queue: State<'_, std::sync::Arc<core::downloader::TaskQueue>>,
+    id: String,
+) -> Result<bool, String> {
+    Ok(task_queue.remove(&id))
+}
+
+/// Download the queued tasks, including tasks added while it runs, until the queue is empty.
+/// `options` overrides the download settings for this batch.
+#[tauri::command]
+#[dropout_macros::api]
+async fn drain_downloads(
+    window: Window,
+    config_state: State<'_, core::config::ConfigState>,
+    task_queue: State<'_, std::sync::Arc<core::downloader::TaskQueue>>,
+    options: Option<core::downloader::BatchOptions>,
+) -> Result<core::downloader::DownloadBatchResult, core::downloader::DownloadError> {
+    let config = config_state.config.lock().unwrap().clone();
+    let client = core::downloader::shared_client(window.app_handle())
+        .map_err(core::downloader::DownloadError::other)?;
+    let options = options
+        .unwrap_or_default()
+        .apply(core::downloader::DownloadOptions {
+            state_dir: core::downloader::batch_state_dir(window.app_handle()),
+            client: Some(client),
+            ..core::downloader::DownloadOptions::from_config(&config)
+        });
+    let sink = std::sync::Arc::new(core::downloader::WindowSink(window));
+    core::downloader::drain_queue(sink, task_queue.inner().clone(), options).await
+}
+
+/// Set the download bandwidth limit in bytes per second (0 = unlimited), applied to running batches too
+#[tauri::command]
+#[dropout_macros::api]
+async fn set_download_speed_limit(
+    config_state: State<'_, core::config::ConfigState>,
+    download_manager: State<'_, core::downloader::DownloadManager>,
+    bytes_per_sec: u64,
+) -> Result<(), String> {
+    config_state.config.lock().unwrap().download_speed_limit = bytes_per_sec;
+    config_state.save()?;
+    download_manager.set_speed_limit(bytes_per_sec);
+    Ok(())
+}
+
+/// Set the URL rewrite rules used to route downloads through mirrors
+#[tauri::command]
+#[dropout_macros::api]
+async fn set_url_rewrite_rules(
+    config_state: State<'_, core::config::ConfigState>,
+    rules: Vec<core::downloader::UrlRewriteRule>,
+) -> Result<(), String> {
+    config_state.config.lock().unwrap().url_rewrite_rules = rules;
+    config_state.save()
+}
+
+/// Get the preset URL rewrite rules for the BMCLAPI mirror
+#[tauri::command]
+#[dropout_macros::api]
+async fn get_bmclapi_rewrite_rules() -> Result<Vec<core::downloader::UrlRewriteRule>, String> {
+    Ok(core::downloader::UrlRewriter::bmclapi_rules())
+}
+
+/// Save the proxy and connection settings and rebuild the shared HTTP client with them.
+/// Batches already running keep their connections.
+#[tauri::command]
+#[dropout_macros::api]
+async fn update_network_settings(
+    config_state: State<'_, core::config::ConfigState>,
+    http_client: State<'_, core::downloader::HttpClient>,
+    proxy: core::downloader::ProxyConfig,
+    network: core::downloader::NetworkSettings,
+) -> Result<(), String> {
+    network.validate()?;
+    let options = {
+        let mut config = config_state.config.lock().unwrap();
+        config.proxy = proxy;
+        config.network = network;
+        core::downloader::DownloadOptions::from_config(&config)
+    };
+    http_client.rebuild(&options)?;
+    config_state.save()
+}
+
+/// Send a small request through the configured proxy and return the latency in milliseconds
+#[tauri::command]
+#[dropout_macros::api]
+async fn test_proxy(config_state: State<'_, core::config::ConfigState>) -> Result<u64, String> {
+    let proxy = config_state.config.lock().unwrap().proxy.clone();
+    core::downloader::test_proxy(&proxy).await
+}
+
+/// Download a small sample from each mirror and report their latency and speed, so the
+/// fastest one can be picked
+#[tauri::command]
+#[dropout_macros::api]
+async fn benchmark_mirrors(
+    app_handle: tauri::AppHandle,
+    urls: Vec<String>,
+    sample_path: String,
+) -> Result<Vec<core::downloader::MirrorBenchmark>, String> {
+    let client = core::downloader::shared_client(&app_handle)?;
+    Ok(core::downloader::benchmark_mirrors(&client, &urls, &sample_path).await)
+}
+
 /// Get Minecraft versions supported by Fabric
 #[tauri::command]
 #[dropout_macros::api]
@@ -2600,8 +3028,16 @@ fn main() {
         .manage(core::auth::AccountState::new())
         .manage(MsRefreshTokenState::new())
         .manage(core::assistant::AssistantState::new())
+        .manage(core::downloader::DownloadManager::new())
+        .manage(std::sync::Arc::new(core::downloader::TaskQueue::new()))
         .setup(|app| {
             let config_state = core::config::ConfigState::new(app.handle());
+            let options = core::downloader::DownloadOptions::from_config(
+                &config_state.config.lock().unwrap(),
+            );
+            app.manage(core::downloader::HttpClient::new(&options));
+            app.state::<core::downloader::DownloadManager>()
+                .set_speed_limit(options.speed_limit);
             app.manage(config_state);
 
             // Initialize instance state
@@ -2639,6 +3075,50 @@ fn main() {
                 let _ = app.emit("pending-java-downloads", pending.len());
             }
 
+            // Forget download batches that were interrupted too long ago
+            let mut resumable_parts = std::collections::HashSet::new();
+            if let Some(dir) = core::downloader::batch_state_dir(app.handle()) {
+                let config_state: State<core::config::ConfigState> = app.state();
+                let max_age_days = config_state
+                    .config
+                    .lock()
+                    .unwrap()
+                    .download_state_max_age_days;
+                core::downloader::collect_stale_batches(
+                    &dir,
+                    std::time::Duration::from_secs(u64::from(max_age_days) * 24 * 60 * 60),
+                );
+                resumable_parts.extend(
+                    core::downloader::pending_batches(&dir)
+                        .into_iter()
+                        .flat_map(|batch| batch.tasks)
+                        .map(|pending| core::downloader::part_path(&pending.task.path)),
+                );
+            }
+
+            // Delete partial files that crashed downloads left in the instance directories.
+            // Walking them can take a while, so it doesn't hold up the startup.
+            let config_state: State<core::config::ConfigState> = app.state();
+            let max_age_days = config_state.config.lock().unwrap().orphan_file_max_age_days;
+            let max_age = std::time::Duration::from_secs(u64::from(max_age_days) * 24 * 60 * 60);
+            let instance_state: State<core::instance::InstanceState> = app.state();
+            let game_dirs: Vec<_> = instance_state
+                .list_instances()
+                .into_iter()
+                .map(|instance| instance.game_dir)
+                .collect();
+            std::thread::spawn(move || {
+                let removed: usize = game_dirs
+                    .iter()
+                    .map(|dir| {
+                        core::downloader::sweep_orphaned_files(dir, max_age, &resumable_parts)
+                    })
+                    .sum();
+                if removed > 0 {
+                    println!("[Startup] Deleted {} orphaned partial file(s)", removed);
+                }
+            });
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -2664,6 +3144,8 @@ fn main() {
             refresh_account,
             // Java commands
             detect_java,
+            detect_javas,
+            refresh_javas,
             get_recommended_java,
             fetch_adoptium_java,
             download_adoptium_java,
@@ -2673,6 +3155,25 @@ fn main() {
             cancel_java_download,
             get_pending_java_downloads,
             resume_java_downloads,
+            // Download commands
+            pause_download,
+            resume_download,
+            cancel_task,
+            enqueue_downloads,
+            verify_files,
+            retry_failed,
+            get_pending_batches,
+            resume_batch,
+            prune_download_cache,
+            plan_download,
+            remove_download,
+            drain_downloads,
+            set_download_speed_limit,
+            set_url_rewrite_rules,
+            get_bmclapi_rewrite_rules,
+            test_proxy,
+            benchmark_mirrors,
+            update_network_settings,
             // Fabric commands
             get_fabric_game_versions,
             get_fabric_loader_versions,
@@ -2705,6 +3206,37 @@ fn main() {
             delete_instance_file,
             open_file_explorer
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app_handle, event| match event {
+            // Give running downloads a moment to write out their data before exiting
+            tauri::RunEvent::ExitRequested { api, .. } => {
+                let manager = app_handle.state::<core::downloader::DownloadManager>();
+                if !manager.has_running() || manager.is_shutting_down() {
+                    return;
+                }
+                api.prevent_exit();
+                let config_state: State<core::config::ConfigState> = app_handle.state();
+                let grace_secs = config_state
+                    .config
+                    .lock()
+                    .unwrap()
+                    .download_shutdown_grace_secs;
+                let app_handle = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    app_handle
+                        .state::<core::downloader::DownloadManager>()
+                        .shutdown(std::time::Duration::from_secs(u64::from(grace_secs)))
+                        .await;
+                    app_handle.exit(0);
+                });
+            }
+            // Save running download batches so they can be resumed on the next start
+            tauri::RunEvent::Exit => {
+                app_handle
+                    .state::<core::downloader::DownloadManager>()
+                    .save_all();
+            }
+            _ => {}
+        });
 }
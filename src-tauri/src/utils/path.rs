@@ -1,22 +1,72 @@
 /// Path utilities for cross-platform compatibility
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Longest path the Win32 file APIs accept without the extended-length prefix
+#[cfg(target_os = "windows")]
+const MAX_PATH: usize = 260;
 
 /// Helper to strip UNC prefix on Windows (\\?\)
-/// This is needed because std::fs::canonicalize adds UNC prefix on Windows
+/// This is needed because std::fs::canonicalize adds UNC prefix on Windows.
+/// Network shares (\\?\UNC\server\share) are turned back into \\server\share.
 #[cfg(target_os = "windows")]
-fn strip_unc_prefix(path: PathBuf) -> PathBuf {
-    let s = path.to_string_lossy().to_string();
-    if s.starts_with(r"\\?\") {
-        return PathBuf::from(&s[4..]);
+pub fn strip_unc_prefix(path: PathBuf) -> PathBuf {
+    let s = path.to_string_lossy();
+    if let Some(share) = s.strip_prefix(r"\\?\UNC\") {
+        return PathBuf::from(format!(r"\\{}", share));
+    }
+    if let Some(local) = s.strip_prefix(r"\\?\") {
+        return PathBuf::from(local);
     }
     path
 }
 
 #[cfg(not(target_os = "windows"))]
-fn strip_unc_prefix(path: PathBuf) -> PathBuf {
+pub fn strip_unc_prefix(path: PathBuf) -> PathBuf {
     path
 }
 
+/// Add the extended-length prefix (\\?\ or \\?\UNC\ for shares) to absolute paths
+/// longer than MAX_PATH, so files deep inside an instance can still be created.
+/// Shorter, relative and already prefixed paths are returned unchanged.
+#[cfg(target_os = "windows")]
+pub fn extended_length_path(path: &Path) -> PathBuf {
+    let s = path.to_string_lossy();
+    if s.len() < MAX_PATH || !path.is_absolute() || s.starts_with(r"\\?\") {
+        return path.to_path_buf();
+    }
+    // The prefix turns off the path parsing of Win32, so separators must be backslashes
+    let s = s.replace('/', r"\");
+    match s.strip_prefix(r"\\") {
+        Some(share) => PathBuf::from(format!(r"\\?\UNC\{}", share)),
+        None => PathBuf::from(format!(r"\\?\{}", s)),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn extended_length_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+/// Whether an I/O error comes from a network share going away or being unreachable,
+/// which another attempt may get past
+pub fn is_network_error(e: &std::io::Error) -> bool {
+    // ERROR_BAD_NETPATH, ERROR_NETWORK_BUSY, ERROR_UNEXP_NET_ERR, ERROR_NETNAME_DELETED,
+    // ERROR_BAD_NET_NAME and ERROR_NETWORK_UNREACHABLE
+    #[cfg(target_os = "windows")]
+    if matches!(e.raw_os_error(), Some(53 | 54 | 59 | 64 | 67 | 1231)) {
+        return true;
+    }
+    matches!(
+        e.kind(),
+        std::io::ErrorKind::ConnectionReset
+            | std::io::ErrorKind::ConnectionAborted
+            | std::io::ErrorKind::NetworkDown
+            | std::io::ErrorKind::NetworkUnreachable
+            | std::io::ErrorKind::HostUnreachable
+            | std::io::ErrorKind::StaleNetworkFileHandle
+    )
+}
+
 /// Normalize a Java executable path for the current platform.
 ///
 /// This function handles platform-specific requirements and validates that
@@ -235,6 +285,13 @@ mod tests {
             let normal_path = PathBuf::from(r"C:\Windows\System32\cmd.exe");
             let unchanged = strip_unc_prefix(normal_path.clone());
             assert_eq!(unchanged, normal_path);
+
+            let share = PathBuf::from(r"\\?\UNC\server\share\minecraft");
+            let stripped = strip_unc_prefix(share);
+            assert_eq!(stripped.to_string_lossy(), r"\\server\share\minecraft");
+
+            let share = PathBuf::from(r"\\server\share\minecraft");
+            assert_eq!(strip_unc_prefix(share.clone()), share);
         }
 
         #[cfg(not(target_os = "windows"))]
@@ -244,4 +301,47 @@ mod tests {
             assert_eq!(unchanged, path);
         }
     }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_extended_length_path_windows() {
+        let short = PathBuf::from(r"C:\Games\minecraft\options.txt");
+        assert_eq!(extended_length_path(&short), short);
+
+        let deep = "a".repeat(300);
+        let local = PathBuf::from(format!(r"C:\Games\{}", deep));
+        assert_eq!(
+            extended_length_path(&local).to_string_lossy(),
+            format!(r"\\?\C:\Games\{}", deep)
+        );
+
+        let share = PathBuf::from(format!(r"\\server\share\{}", deep));
+        assert_eq!(
+            extended_length_path(&share).to_string_lossy(),
+            format!(r"\\?\UNC\server\share\{}", deep)
+        );
+
+        // Already prefixed paths must not be prefixed twice
+        let prefixed = extended_length_path(&share);
+        assert_eq!(extended_length_path(&prefixed), prefixed);
+
+        let relative = PathBuf::from(deep);
+        assert_eq!(extended_length_path(&relative), relative);
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_is_network_error_windows() {
+        // ERROR_NETNAME_DELETED
+        assert!(is_network_error(&std::io::Error::from_raw_os_error(64)));
+        // ERROR_ACCESS_DENIED
+        assert!(!is_network_error(&std::io::Error::from_raw_os_error(5)));
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_extended_length_path_unix() {
+        let path = PathBuf::from(format!("/srv/{}", "a".repeat(300)));
+        assert_eq!(extended_length_path(&path), path);
+    }
 }
@@ -113,3 +113,134 @@ pub fn extract_tar_gz(archive_path: &Path, extract_to: &Path) -> Result<String,
 
     top_level_dir.ok_or_else(|| "Archive appears to be empty".to_string())
 }
+
+/// Extract a zip archive, calling `on_entry` with the number of extracted entries and the
+/// total number of entries after each one. With `executable` set every extracted file can be
+/// executed.
+///
+/// Unlike `extract_zip`, every entry is kept and entries whose path would escape
+/// `extract_to` fail the extraction instead of being skipped.
+pub fn unpack_zip(
+    zip_path: &Path,
+    extract_to: &Path,
+    executable: bool,
+    mut on_entry: impl FnMut(usize, usize),
+) -> Result<(), String> {
+    let file = fs::File::open(zip_path)
+        .map_err(|e| format!("Failed to open zip {}: {}", zip_path.display(), e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read zip: {}", e))?;
+    fs::create_dir_all(extract_to)
+        .map_err(|e| format!("Failed to create extract directory: {}", e))?;
+
+    let total = archive.len();
+    for i in 0..total {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read zip entry: {}", e))?;
+        let outpath = match file.enclosed_name() {
+            Some(path) => extract_to.join(path),
+            None => return Err(escaping_entry(file.name())),
+        };
+
+        if file.is_dir() {
+            fs::create_dir_all(&outpath).map_err(|e| format!("Failed to create dir: {}", e))?;
+        } else {
+            if let Some(parent) = outpath.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("Failed to create dir: {}", e))?;
+            }
+            let mut outfile =
+                fs::File::create(&outpath).map_err(|e| format!("Failed to create file: {}", e))?;
+            std::io::copy(&mut file, &mut outfile)
+                .map_err(|e| format!("Failed to copy file: {}", e))?;
+        }
+
+        #[cfg(unix)]
+        if let Some(mode) = file.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&outpath, fs::Permissions::from_mode(mode & 0o7777));
+        }
+        if executable && !file.is_dir() {
+            add_execute_permission(&outpath)
+                .map_err(|e| format!("Failed to set permissions: {}", e))?;
+        }
+        on_entry(i + 1, total);
+    }
+
+    Ok(())
+}
+
+/// Extract a tar.gz archive, calling `on_entry` with the number of extracted entries after
+/// each one.
+///
+/// Entries whose path would escape `extract_to` fail the extraction. Unix permission bits
+/// are kept, and with `executable` set every extracted file can be executed.
+pub fn unpack_tar_gz(
+    archive_path: &Path,
+    extract_to: &Path,
+    executable: bool,
+    mut on_entry: impl FnMut(usize),
+) -> Result<(), String> {
+    let file = fs::File::open(archive_path)
+        .map_err(|e| format!("Failed to open tar.gz {}: {}", archive_path.display(), e))?;
+    let mut archive = Archive::new(GzDecoder::new(file));
+    fs::create_dir_all(extract_to)
+        .map_err(|e| format!("Failed to create extract directory: {}", e))?;
+
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("Failed to read tar entries: {}", e))?;
+    for (i, entry) in entries.enumerate() {
+        let mut entry = entry.map_err(|e| format!("Failed to read tar entry: {}", e))?;
+        let entry_path = entry
+            .path()
+            .map_err(|e| format!("Failed to get entry path: {}", e))?
+            .into_owned();
+        let escapes = entry_path.components().any(|c| {
+            !matches!(
+                c,
+                std::path::Component::Normal(_) | std::path::Component::CurDir
+            )
+        });
+        if escapes {
+            return Err(escaping_entry(&entry_path.to_string_lossy()));
+        }
+
+        // `unpack_in` also refuses to write through symlinks pointing outside `extract_to`
+        let unpacked = entry
+            .unpack_in(extract_to)
+            .map_err(|e| format!("Failed to extract {}: {}", entry_path.display(), e))?;
+        if !unpacked {
+            return Err(escaping_entry(&entry_path.to_string_lossy()));
+        }
+        if executable && entry.header().entry_type().is_file() {
+            add_execute_permission(&extract_to.join(&entry_path))
+                .map_err(|e| format!("Failed to set permissions: {}", e))?;
+        }
+        on_entry(i + 1);
+    }
+
+    Ok(())
+}
+
+/// Add the execute bits to the permissions of a file, keeping the others. Does nothing on
+/// Windows, where files aren't marked executable.
+pub fn add_execute_permission(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut permissions = fs::metadata(path)?.permissions();
+        permissions.set_mode(permissions.mode() | 0o111);
+        fs::set_permissions(path, permissions)?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+fn escaping_entry(name: &str) -> String {
+    format!(
+        "Archive entry '{}' points outside the extraction directory",
+        name
+    )
+}